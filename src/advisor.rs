@@ -0,0 +1,219 @@
+use crate::agents::ismcts::Sim;
+use crate::components::card::Card;
+use crate::game::GameView;
+use crate::scoring::ScoringRule;
+use crate::tracker::CardTracker;
+use rand::RngCore;
+
+/// Hints for a person at the table. The rest of the round is played out
+/// `samples` times with the unseen cards dealt at random, everyone playing
+/// like `HeuristicAgent`, and the outcomes are counted.
+#[derive(Clone, Copy, Debug)]
+pub struct Advisor {
+    pub samples: u32,
+}
+impl Default for Advisor {
+    fn default() -> Self {
+        Advisor { samples: 200 }
+    }
+}
+
+/// What `Advisor::advise_bet` recommends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetAdvice {
+    /// The legal bet that scored best on average.
+    pub bet: u8,
+    /// Chance of winning exactly `n` tricks after making that bet, by `n`.
+    pub tricks: Vec<f32>,
+}
+
+/// How one legal card fared in `Advisor::advise_card`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CardAdvice {
+    pub card: Card,
+    /// Chance the card takes the current trick.
+    pub win_trick: f32,
+    /// Chance of ending the round with exactly the tricks bet.
+    pub make_bet: f32,
+}
+
+impl Advisor {
+    /// Best bet for the hand in `view`, scored by `view.scoring`. Out of
+    /// turn, when `view.legal_bets` is empty, every bet up to the round counts.
+    pub fn advise_bet(&self, view: &GameView, rng: &mut dyn RngCore) -> BetAdvice {
+        let seat = view.seat.index();
+        let legal_bets = match view.legal_bets.is_empty() {
+            true => (0..=view.round_num as u8).collect(),
+            false => view.legal_bets.to_vec(),
+        };
+        let tracker = CardTracker::from_view(view);
+        // Tricks already won count too, there can be up to a full round of them.
+        let mut counts = vec![vec![0u32; view.round_num + 1]; legal_bets.len()];
+        let mut points = vec![0i64; legal_bets.len()];
+
+        for _ in 0..self.samples {
+            let sim = Sim::deal_unseen(view, &tracker, rng);
+            for (i, bet) in legal_bets.iter().enumerate() {
+                let mut sim = sim.clone();
+                sim.place_bets(*bet);
+                sim.play_out();
+                let tricks = sim.tricks[seat];
+                counts[i][tricks as usize] += 1;
+                points[i] += view.scoring.score(*bet, tricks) as i64;
+            }
+        }
+
+        let best = (0..legal_bets.len())
+            .max_by_key(|i| points[*i])
+            .unwrap_or(0);
+        BetAdvice {
+            bet: legal_bets[best],
+            tricks: counts[best]
+                .iter()
+                .map(|count| *count as f32 / self.samples.max(1) as f32)
+                .collect(),
+        }
+    }
+
+    /// Every legal card in `view`, most likely to make the bet first.
+    pub fn advise_card(&self, view: &GameView, rng: &mut dyn RngCore) -> Vec<CardAdvice> {
+        let seat = view.seat.index();
+        let bet = view.bets[seat].unwrap_or(0);
+        let tracker = CardTracker::from_view(view);
+        let legal = view.legal_cards();
+        let mut won = vec![0u32; legal.len()];
+        let mut made = vec![0u32; legal.len()];
+
+        for _ in 0..self.samples {
+            let sim = Sim::deal_unseen(view, &tracker, rng);
+            for (i, card) in legal.iter().enumerate() {
+                let mut sim = sim.clone();
+                let before = sim.tricks[seat];
+                sim.play(*card);
+                sim.finish_trick();
+                if sim.tricks[seat] > before {
+                    won[i] += 1;
+                }
+                sim.play_out();
+                if sim.tricks[seat] == bet {
+                    made[i] += 1;
+                }
+            }
+        }
+
+        let chance = |count: u32| count as f32 / self.samples.max(1) as f32;
+        let mut advice: Vec<CardAdvice> = legal
+            .iter()
+            .enumerate()
+            .map(|(i, card)| CardAdvice {
+                card: *card,
+                win_trick: chance(won[i]),
+                make_bet: chance(made[i]),
+            })
+            .collect();
+        advice.sort_by(|a, b| b.make_bet.total_cmp(&a.make_bet));
+        advice
+    }
+}
+
+#[test]
+pub fn test_advise_bet() {
+    use crate::agents::heuristic::test_view;
+    use crate::config::Scoring;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let advisor = Advisor { samples: 50 };
+
+    let advice = advisor.advise_bet(&test_view("W W2", "2H", vec![None; 4]), &mut rng);
+    assert_eq!(advice.bet, 2);
+    assert_eq!(advice.tricks.len(), 3);
+    assert!(advice.tricks[2] > 0.9);
+    let total: f32 = advice.tricks.iter().sum();
+    assert!((total - 1.0).abs() < 0.01);
+
+    let advice = advisor.advise_bet(&test_view("Je 2C", "2H", vec![None; 4]), &mut rng);
+    assert_eq!(advice.bet, 0);
+
+    // The bet is made for the game's scoring rule.
+    let mut view = test_view("AH 2C", "2H", vec![None; 4]);
+    assert_eq!(advisor.advise_bet(&view, &mut rng).bet, 1);
+    view.scoring = Scoring::ZeroBidBonus(1000);
+    assert_eq!(advisor.advise_bet(&view, &mut rng).bet, 0);
+}
+
+#[test]
+pub fn test_advise_bet_after_betting() {
+    use crate::agents::heuristic::test_view;
+    use crate::components::card::Suit;
+    use crate::history::Trick;
+    use crate::table::PlayerId;
+    use crate::Play;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Seat 1 took the first trick of round 3 and leads with two Wizards left.
+    let mut view = test_view("W W2", "2D", vec![Some(3), Some(3), Some(0), Some(0)]);
+    let plays: Vec<Play> = [(1, "AH"), (2, "2H"), (3, "3H"), (0, "4H")]
+        .iter()
+        .map(|(seat, code)| Play {
+            card: code.parse().unwrap(),
+            seat: PlayerId(*seat),
+        })
+        .collect();
+    view.played = plays.iter().map(|play| play.card).collect();
+    let past_tricks = [Trick {
+        leader: PlayerId(1),
+        lead_suit: Suit::Heart,
+        winner: plays[0],
+        plays,
+    }];
+    view.past_tricks = &past_tricks;
+    view.round_num = 3;
+    view.tricks = &[0, 1, 0, 0];
+    view.legal_bets = &[];
+
+    let advice = Advisor { samples: 20 }.advise_bet(&view, &mut StdRng::seed_from_u64(5));
+    assert_eq!(advice.bet, 3);
+    assert_eq!(advice.tricks, vec![0.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+pub fn test_advise_card() {
+    use crate::agents::heuristic::test_view;
+    use crate::components::card::Suit;
+    use crate::table::PlayerId;
+    use crate::Play;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let advisor = Advisor { samples: 50 };
+    let card = |code: &str| code.parse::<Card>().unwrap();
+
+    // Last to play into a spade trick, holding the ace and the deuce.
+    let mut view = test_view("AS 2S 3C", "2H", vec![Some(1); 4]);
+    view.leader = PlayerId(2);
+    let trick: Vec<Play> = [(2, "QS"), (3, "KS"), (0, "3S")]
+        .iter()
+        .map(|(seat, code)| Play {
+            card: card(code),
+            seat: PlayerId(*seat),
+        })
+        .collect();
+    view.trick = &trick;
+    view.played = view.trick.iter().map(|play| play.card).collect();
+    view.lead_suit = Suit::Spade;
+
+    let advice = advisor.advise_card(&view, &mut rng);
+    assert_eq!(advice.len(), 2);
+    assert_eq!(advice[0].card, card("AS"));
+    assert_eq!(advice[0].win_trick, 1.0);
+    assert_eq!(advice[1].win_trick, 0.0);
+
+    // With no tricks wanted, ducking comes first.
+    view.bets = &[Some(1), Some(0), Some(1), Some(1)];
+    let advice = advisor.advise_card(&view, &mut rng);
+    assert_eq!(advice[0].card, card("2S"));
+}
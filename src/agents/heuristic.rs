@@ -0,0 +1,291 @@
+use crate::agents::PlayerAgent;
+use crate::components::card::{Card, Rank, Suit};
+use crate::components::card_set::CardSet;
+use crate::game::GameView;
+use crate::{calc_winner_of_trick, legal_card_set, Play};
+use rand::RngCore;
+use std::iter;
+
+/// Computer player that bets what its hand is worth and then plays to make
+/// exactly that many tricks.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HeuristicAgent;
+impl PlayerAgent for HeuristicAgent {
+    /// The suit with the most cards in hand, the higher cards break ties.
+    fn choose_trump_suit(&mut self, view: &GameView, _rng: &mut dyn RngCore) -> Suit {
+        let hand = view.hand;
+        let strength = |suit: Suit| {
+            let cards = hand.of_suit(suit);
+            let ranks: u32 = cards.cards().map(|card| card.rank.value() as u32).sum();
+            (cards.len(), ranks)
+        };
+        Suit::ALL
+            .iter()
+            .copied()
+            .max_by_key(|suit| strength(*suit))
+            .unwrap_or(Suit::Club)
+    }
+
+    /// The legal bet closest to `expected_tricks`, the lower one on a tie.
+    fn choose_bet(&mut self, view: &GameView, _rng: &mut dyn RngCore) -> u8 {
+        let expected = expected_tricks(view);
+        let distance = |bet: &u8| ((*bet as f32 - expected).abs() * 100.0) as u32;
+        view.legal_bets
+            .iter()
+            .copied()
+            .min_by_key(|bet| (distance(bet), *bet))
+            .unwrap_or(0)
+    }
+
+    /// Short of its bet it wins as cheaply as it can and throws away Jesters
+    /// when it can't. Once the bet is made it ducks with its highest losing
+    /// card, saving Jesters for when nothing else loses.
+    fn choose_card(&mut self, view: &GameView, _rng: &mut dyn RngCore) -> Card {
+        let seat = view.seat.index();
+        let bet = view.bets[seat].unwrap_or(0);
+        let needed = bet.saturating_sub(view.tricks[seat]);
+        let legal = legal_card_set(view.hand, view.lead_suit);
+        pick_card(legal, view.trick, view.trump_suit(), needed)
+            .expect("asked to play from an empty hand")
+    }
+}
+
+/// The card `HeuristicAgent` plays from `legal` into `trick` when it still
+/// needs `needed` tricks, None if `legal` is empty.
+pub(crate) fn pick_card(
+    legal: CardSet,
+    trick: &[Play],
+    trump_suit: Suit,
+    needed: u8,
+) -> Option<Card> {
+    let strength = |card: &Card| strength(*card, trump_suit);
+
+    if trick.is_empty() {
+        let lead = if needed > 0 {
+            legal.cards().max_by_key(strength)
+        } else {
+            let safe = legal.cards().filter(|card| card.rank != Rank::Jester);
+            safe.min_by_key(strength)
+        };
+        return lead.or_else(|| legal.cards().next());
+    }
+
+    let winners: CardSet = legal
+        .cards()
+        .filter(|card| takes_trick(trick, *card, trump_suit))
+        .collect();
+    let losers = legal - winners;
+    if needed > 0 {
+        winners
+            .cards()
+            .min_by_key(strength)
+            .or_else(|| losers.cards().min_by_key(strength))
+    } else {
+        // Failing a duck, a Jester if there is one, otherwise win with the
+        // card most likely to cause trouble later.
+        let ducks = losers.cards().filter(|card| card.rank != Rank::Jester);
+        ducks
+            .max_by_key(strength)
+            .or_else(|| losers.cards().next())
+            .or_else(|| winners.cards().max_by_key(strength))
+    }
+}
+
+/// Rough number of tricks the hand in `view` should take this round.
+///
+/// Wizards are almost sure tricks, high trumps and off-suit aces likely ones,
+/// and short side suits let low trumps win too. High cards are worth less the
+/// more players there are, and a little more the later the seat plays into
+/// the first trick. The total is capped by what the bets already placed leave.
+pub fn expected_tricks(view: &GameView) -> f32 {
+    let num_players = view.bets.len();
+    let seat = (view.seat.index() + num_players - view.leader.index()) % num_players.max(1);
+    let hand = view.hand;
+    let expected = hand_value(hand, view.trump_suit(), num_players, seat);
+
+    // Other players already claimed some of the tricks.
+    let hand_size = hand.len() as f32;
+    let wizards = (hand & CardSet::WIZARDS).len() as f32;
+    let claimed: u8 = view.bets.iter().flatten().sum();
+    let left = (hand_size - claimed as f32).max(wizards);
+    expected.min(left).min(hand_size).max(0.0)
+}
+
+/// Tricks `hand` should take on its own, not counting anyone's bets. `seat`
+/// is how many players play before it in the first trick.
+pub(crate) fn hand_value(hand: CardSet, trump_suit: Suit, num_players: usize, seat: usize) -> f32 {
+    let hand_size = hand.len() as f32;
+
+    // Each opponent is another chance of the card being beaten.
+    let crowd = 1.0 - 0.07 * (num_players.saturating_sub(3) as f32);
+    let position = 1.0 + 0.03 * seat as f32;
+
+    let wizards = (hand & CardSet::WIZARDS).len() as f32;
+    let mut expected = wizards * 0.95;
+
+    let trumps = hand.of_suit(trump_suit);
+    for card in trumps.cards() {
+        expected += trump_value(card, trumps.len()) * crowd * position;
+    }
+
+    for suit in Suit::ALL.iter().filter(|suit| **suit != trump_suit) {
+        let cards = hand.of_suit(*suit);
+        for card in cards.cards() {
+            expected += side_value(card, cards.len(), hand_size) * crowd * position;
+        }
+        // Running out of a suit early lets spare trumps win tricks.
+        if cards.len() < 2 && trumps.len() > 2 && hand_size > 3.0 {
+            expected += 0.3 * (2 - cards.len()) as f32;
+        }
+    }
+    expected
+}
+
+/// How hard a card is to beat: Wizards, then trumps, then the rest by rank.
+fn strength(card: Card, trump_suit: Suit) -> u8 {
+    match card.rank {
+        Rank::Wizard => 100,
+        Rank::Jester => 0,
+        _ if card.suit == trump_suit => 20 + card.rank.value(),
+        _ => card.rank.value(),
+    }
+}
+
+/// True if `card` would be winning `trick` once played into it.
+fn takes_trick(trick: &[Play], card: Card, trump_suit: Suit) -> bool {
+    let cards = trick.iter().map(|play| &play.card).chain(iter::once(&card));
+    calc_winner_of_trick(cards, trump_suit) == Some(trick.len())
+}
+
+fn trump_value(card: Card, num_trumps: usize) -> f32 {
+    let high = match card.rank {
+        Rank::Ace => 0.9,
+        Rank::King => 0.8,
+        Rank::Queen => 0.65,
+        Rank::Jack => 0.5,
+        Rank::Ten => 0.4,
+        _ => 0.2,
+    };
+    // Length in trumps makes even the small ones count.
+    if num_trumps >= 4 {
+        f32::max(high, 0.5)
+    } else {
+        high
+    }
+}
+
+fn side_value(card: Card, suit_len: usize, hand_size: f32) -> f32 {
+    // Long side suits get trumped before the high cards come good.
+    let length = if suit_len > 3 { 0.7 } else { 1.0 };
+    let value = match card.rank {
+        Rank::Ace => 0.7,
+        Rank::King if suit_len <= 2 && hand_size > 2.0 => 0.35,
+        Rank::King => 0.2,
+        Rank::Queen if hand_size > 8.0 => 0.1,
+        _ => 0.0,
+    };
+    value * length
+}
+
+/// View of seat 1 before any card is played. The slices it borrows are
+/// leaked, tests point them at their own data to change them.
+#[cfg(test)]
+pub(crate) fn test_view(hand: &str, trump: &str, bets: Vec<Option<u8>>) -> GameView<'static> {
+    use crate::components::deck::Deck;
+    use crate::table::PlayerId;
+
+    let hand: CardSet = hand.parse::<Deck>().unwrap().into();
+    let num_players = bets.len();
+    GameView {
+        seat: PlayerId(1),
+        trump: trump.parse().ok(),
+        lead_suit: Suit::Suitless,
+        trick: &[],
+        played: CardSet::EMPTY,
+        past_tricks: &[],
+        round_num: hand.len(),
+        num_rounds: 15,
+        dealer: PlayerId(0),
+        leader: PlayerId(1),
+        bets: bets.leak(),
+        legal_bets: (0..=hand.len() as u8).collect::<Vec<_>>().leak(),
+        tricks: vec![0; num_players].leak(),
+        scores: vec![0; num_players].leak(),
+        scoring: crate::config::Scoring::Official,
+        hand,
+    }
+}
+
+#[test]
+pub fn test_heuristic_bets() {
+    use rand::rngs::mock::StepRng;
+
+    let mut rng = StepRng::new(0, 1);
+    let mut bet =
+        |hand, trump, bets| HeuristicAgent.choose_bet(&test_view(hand, trump, bets), &mut rng);
+    let open = vec![None; 4];
+
+    // Wizards and top trumps win, low off-suit cards and Jesters don't.
+    assert_eq!(bet("W W2 AH", "2H", open.clone()), 3);
+    assert_eq!(bet("Je 2C 3D 5S", "2H", open.clone()), 0);
+    assert_eq!(bet("W 2C 3D 5S", "2H", open.clone()), 1);
+    assert_eq!(bet("AC AD 4S 5S", "2H", open.clone()), 1);
+
+    // The same hand is worth less when the others claimed most tricks.
+    assert_eq!(bet("W AH KH 2C", "2H", open.clone()), 3);
+    assert_eq!(
+        bet("W AH KH 2C", "2H", vec![Some(2), None, None, Some(1)]),
+        1
+    );
+
+    // A bet the rules don't allow is never picked.
+    let mut view = test_view("W W2 AH", "2H", open);
+    view.legal_bets = &[0, 1, 2];
+    assert_eq!(HeuristicAgent.choose_bet(&view, &mut rng), 2);
+}
+
+#[test]
+pub fn test_heuristic_trump_suit() {
+    use rand::rngs::mock::StepRng;
+
+    let view = test_view("2C 3C AH W Je", "W", vec![None; 3]);
+    let suit = HeuristicAgent.choose_trump_suit(&view, &mut StepRng::new(0, 1));
+    assert_eq!(suit, Suit::Club);
+}
+
+#[test]
+pub fn test_heuristic_play() {
+    use rand::rngs::mock::StepRng;
+
+    let mut rng = StepRng::new(0, 1);
+    let card = |code: &str| code.parse::<Card>().unwrap();
+    let mut play = |hand, bet, trick: &str| {
+        let mut view = test_view(hand, "2H", vec![Some(bet); 4]);
+        let trick: Vec<Play> = trick
+            .split_whitespace()
+            .map(|code| Play {
+                card: card(code),
+                seat: crate::table::PlayerId(0),
+            })
+            .collect();
+        view.trick = &trick;
+        view.lead_suit = crate::lead_suit(view.trick.iter().map(|play| &play.card));
+        HeuristicAgent.choose_card(&view, &mut rng)
+    };
+
+    // Short of the bet: win as cheaply as possible, lead the strongest card.
+    assert_eq!(play("KS AS 3H W", 1, "QS"), card("KS"));
+    assert_eq!(play("3H W", 1, "QS"), card("3H"));
+    assert_eq!(play("2C 9H W", 1, ""), card("W"));
+    // Can't win it, so a Jester goes first.
+    assert_eq!(play("2S Je 3S", 1, "QS"), card("Je"));
+
+    // Bet made: duck with the highest losing card, keep the Jester.
+    assert_eq!(play("2S JS AS Je", 0, "QS"), card("JS"));
+    assert_eq!(play("AS Je", 0, "QS"), card("Je"));
+    assert_eq!(play("AS KS", 0, "QS"), card("AS"));
+    assert_eq!(play("Je 5C 9D", 0, ""), card("5C"));
+
+    // Nothing to play from.
+    assert_eq!(pick_card(CardSet::EMPTY, &[], Suit::Heart, 1), None);
+}
@@ -0,0 +1,496 @@
+use crate::agents::heuristic::{hand_value, pick_card, HeuristicAgent};
+use crate::agents::PlayerAgent;
+use crate::components::card::{Card, CardId, Suit};
+use crate::components::card_set::CardSet;
+use crate::config::Scoring;
+use crate::game::GameView;
+use crate::scoring::ScoringRule;
+use crate::table::PlayerId;
+use crate::tracker::CardTracker;
+use crate::{calc_winner_of_trick, lead_suit, legal_card_set, Play};
+use core::time::Duration;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// How much searching `IsmctsAgent` does for each decision.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Budget {
+    /// Run this many iterations. Seeded games play out the same every time.
+    Iterations(u32),
+    /// Search until the time is up. How well it plays depends on the machine.
+    Time(Duration),
+}
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::Iterations(1000)
+    }
+}
+
+/// Strong computer player using information set Monte Carlo tree search.
+///
+/// Every iteration deals the cards it can't see to the other players at
+/// random, keeping to the suits they are known to be out of and favouring
+/// hands that fit their bets, then walks one search tree shared by all of
+/// those deals. Rounds are played out with `HeuristicAgent`'s rules and
+/// scored by the game's rule. Trump is picked like `HeuristicAgent` does.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IsmctsAgent {
+    pub budget: Budget,
+}
+impl IsmctsAgent {
+    pub fn new(budget: Budget) -> IsmctsAgent {
+        IsmctsAgent { budget }
+    }
+
+    fn search(&self, view: &GameView, rng: &mut dyn RngCore) -> Move {
+        let mut tree = vec![Node::new(None, view.seat.index())];
+        let tracker = CardTracker::from_view(view);
+        let started = Instant::now();
+        let mut iterations = 0;
+        while match self.budget {
+            Budget::Iterations(max) => iterations < max,
+            Budget::Time(time) => iterations == 0 || started.elapsed() < time,
+        } {
+            iterations += 1;
+            let sim = Sim::deal_unseen(view, &tracker, rng);
+            iterate(&mut tree, sim, view.legal_bets, rng);
+        }
+
+        tree[0]
+            .children
+            .iter()
+            .max_by_key(|child| tree[**child].visits)
+            .and_then(|child| tree[*child].mv)
+            .expect("search ran no iterations")
+    }
+}
+impl PlayerAgent for IsmctsAgent {
+    fn choose_trump_suit(&mut self, view: &GameView, rng: &mut dyn RngCore) -> Suit {
+        HeuristicAgent.choose_trump_suit(view, rng)
+    }
+
+    fn choose_bet(&mut self, view: &GameView, rng: &mut dyn RngCore) -> u8 {
+        if let [bet] = view.legal_bets[..] {
+            return bet;
+        }
+        match self.search(view, rng) {
+            Move::Bet(bet) => bet,
+            Move::Play(_) => unreachable!("searched a play while betting"),
+        }
+    }
+
+    fn choose_card(&mut self, view: &GameView, rng: &mut dyn RngCore) -> Card {
+        let legal = view.legal_cards();
+        if let [card] = legal[..] {
+            return card;
+        }
+        match self.search(view, rng) {
+            Move::Play(id) => id.card(),
+            Move::Bet(_) => unreachable!("searched a bet while playing"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Move {
+    Bet(u8),
+    Play(CardId),
+}
+
+/// Node of the search tree. `seat` made the move leading to it, `avail`
+/// counts the iterations where the move could have been made at all.
+#[derive(Debug)]
+struct Node {
+    mv: Option<Move>,
+    seat: usize,
+    children: Vec<usize>,
+    visits: u32,
+    avail: u32,
+    reward: f32,
+}
+impl Node {
+    fn new(mv: Option<Move>, seat: usize) -> Node {
+        Node {
+            mv,
+            seat,
+            children: Vec::new(),
+            visits: 0,
+            avail: 1,
+            reward: 0.0,
+        }
+    }
+
+    fn ucb(&self) -> f32 {
+        let visits = self.visits as f32;
+        self.reward / visits + 0.7 * ((self.avail as f32).ln() / visits).sqrt()
+    }
+}
+
+/// One iteration: select down the tree through moves legal in `sim`, add a
+/// node for a move not tried yet, play the rest out and score it.
+fn iterate(tree: &mut Vec<Node>, mut sim: Sim, legal_bets: &[u8], rng: &mut dyn RngCore) {
+    let mut path = vec![0];
+    let mut node = 0;
+    while !sim.is_over() {
+        let untried = |mv: &Move| !tree[node].children.iter().any(|c| tree[*c].mv == Some(*mv));
+        let num_untried = sim.moves(legal_bets).filter(untried).count();
+        if num_untried > 0 {
+            let pick = rng.gen_range(0..num_untried);
+            let mv = sim.moves(legal_bets).filter(untried).nth(pick).unwrap();
+            let child = tree.len();
+            tree.push(Node::new(Some(mv), sim.turn));
+            tree[node].children.push(child);
+            sim.apply(mv);
+            path.push(child);
+            break;
+        }
+
+        let mut best = None;
+        for i in 0..tree[node].children.len() {
+            let child = tree[node].children[i];
+            if !tree[child]
+                .mv
+                .is_some_and(|mv| sim.is_legal(mv, legal_bets))
+            {
+                continue;
+            }
+            tree[child].avail += 1;
+            let ucb = tree[child].ucb();
+            if best.is_none_or(|(_, score)| ucb > score) {
+                best = Some((child, ucb));
+            }
+        }
+        let (child, _) = best.expect("moves but no children");
+        sim.apply(tree[child].mv.unwrap());
+        path.push(child);
+        node = child;
+    }
+
+    sim.play_out();
+    let rewards = sim.rewards();
+    for node in path {
+        tree[node].visits += 1;
+        tree[node].reward += rewards[tree[node].seat];
+    }
+}
+
+/// The rest of a round with every hand known, cards held as `CardSet`s.
+#[derive(Clone, Debug)]
+pub(crate) struct Sim {
+    hands: Vec<CardSet>,
+    bets: Vec<Option<u8>>,
+    pub(crate) tricks: Vec<u8>,
+    trick: Vec<Play>,
+    trump_suit: Suit,
+    /// Leads the first trick once betting is over.
+    leader: usize,
+    turn: usize,
+    round_num: u8,
+    scoring: Scoring,
+}
+impl Sim {
+    /// A deal of the unseen cards that fits what `view` has seen. A few are
+    /// drawn and the one closest to the other players' bets is kept.
+    pub(crate) fn deal_unseen(
+        view: &GameView,
+        tracker: &CardTracker,
+        rng: &mut dyn RngCore,
+    ) -> Sim {
+        let fit = |sim: &Sim| -> f32 {
+            let num_players = sim.hands.len();
+            (0..num_players)
+                .filter(|seat| *seat != view.seat.index())
+                .filter_map(|seat| {
+                    let needed = sim.bets[seat]?.saturating_sub(sim.tricks[seat]);
+                    let value = hand_value(sim.hands[seat], sim.trump_suit, num_players, 0);
+                    Some((value - needed as f32).abs())
+                })
+                .sum()
+        };
+        (0..3)
+            .map(|_| Sim::deal_once(view, tracker, rng))
+            .min_by(|a, b| fit(a).total_cmp(&fit(b)))
+            .unwrap()
+    }
+
+    fn deal_once(view: &GameView, tracker: &CardTracker, rng: &mut dyn RngCore) -> Sim {
+        let num_players = view.bets.len();
+        let me = view.seat.index();
+        let hand = view.hand;
+        let unseen = tracker.unseen(hand);
+
+        let mut sizes = vec![view.round_num - view.past_tricks.len(); num_players];
+        for play in view.trick {
+            sizes[play.seat.index()] -= 1;
+        }
+
+        let ruled_out = |seat: usize| tracker.ruled_out(PlayerId(seat));
+        let mut hands = vec![CardSet::EMPTY; num_players];
+        hands[me] = hand;
+        let mut others: Vec<usize> = (0..num_players).filter(|seat| *seat != me).collect();
+        // Deal to the most restricted players first so they aren't left short.
+        others.sort_by_key(|seat| (unseen - ruled_out(*seat)).len());
+
+        for attempt in 0..10 {
+            let mut unseen = unseen;
+            let mut dealt = true;
+            for seat in &others {
+                // Give up on the voids rather than fail to deal.
+                let allowed = match attempt {
+                    9 => unseen,
+                    _ => unseen - ruled_out(*seat),
+                };
+                let allowed: Vec<CardId> = allowed.iter().collect();
+                if allowed.len() < sizes[*seat] {
+                    dealt = false;
+                    break;
+                }
+                hands[*seat] = allowed
+                    .choose_multiple(rng, sizes[*seat])
+                    .copied()
+                    .collect();
+                unseen -= hands[*seat];
+            }
+            if dealt {
+                break;
+            }
+        }
+
+        Sim {
+            hands,
+            bets: view.bets.to_vec(),
+            tricks: view.tricks.to_vec(),
+            trick: view.trick.to_vec(),
+            trump_suit: view.trump_suit(),
+            leader: view.leader.index(),
+            turn: me,
+            round_num: view.round_num as u8,
+            scoring: view.scoring,
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.trick.is_empty() && self.hands.iter().all(|hand| hand.is_empty())
+    }
+
+    /// Moves open to the seat whose turn it is, `legal_bets` while betting.
+    fn moves<'a>(&self, legal_bets: &'a [u8]) -> impl Iterator<Item = Move> + 'a {
+        let betting = self.bets[self.turn].is_none();
+        let cards = match betting {
+            true => CardSet::EMPTY,
+            false => self.legal_cards(),
+        };
+        let bets = legal_bets.iter().filter(move |_| betting);
+        bets.map(|bet| Move::Bet(*bet))
+            .chain(cards.iter().map(Move::Play))
+    }
+
+    fn is_legal(&self, mv: Move, legal_bets: &[u8]) -> bool {
+        let betting = self.bets[self.turn].is_none();
+        match mv {
+            Move::Bet(bet) => betting && legal_bets.contains(&bet),
+            Move::Play(id) => !betting && self.legal_cards().contains(id),
+        }
+    }
+
+    /// Cards the seat whose turn it is may play into the trick.
+    fn legal_cards(&self) -> CardSet {
+        let lead = lead_suit(self.trick.iter().map(|play| &play.card));
+        legal_card_set(self.hands[self.turn], lead)
+    }
+
+    fn apply(&mut self, mv: Move) {
+        match mv {
+            Move::Bet(bet) => self.place_bets(bet),
+            Move::Play(id) => self.play(id.card()),
+        }
+    }
+
+    /// Place the searching player's bet, the players after them bet what
+    /// their hand is worth.
+    /// Once betting is over this only swaps in `bet`, the round goes on
+    /// from where it was.
+    pub(crate) fn place_bets(&mut self, bet: u8) {
+        let num_players = self.hands.len();
+        let betting = self.bets[self.turn].is_none();
+        self.bets[self.turn] = Some(bet);
+        for seat in 0..num_players {
+            if self.bets[seat].is_none() {
+                let value = hand_value(self.hands[seat], self.trump_suit, num_players, 0);
+                self.bets[seat] = Some((value.round() as u8).min(self.round_num));
+            }
+        }
+        if betting {
+            self.turn = self.leader;
+        }
+    }
+
+    pub(crate) fn play(&mut self, card: Card) {
+        self.hands[self.turn].remove(card.id);
+        self.trick.push(Play {
+            card,
+            seat: PlayerId(self.turn),
+        });
+
+        let num_players = self.hands.len();
+        if self.trick.len() < num_players {
+            self.turn = (self.turn + 1) % num_players;
+            return;
+        }
+        let cards = self.trick.iter().map(|play| &play.card);
+        let index = calc_winner_of_trick(cards, self.trump_suit).expect("trick is full");
+        let winner = self.trick[index].seat.index();
+        self.tricks[winner] += 1;
+        self.trick.clear();
+        self.turn = winner;
+    }
+
+    /// Finish the round with every seat following `HeuristicAgent`'s rules.
+    pub(crate) fn play_out(&mut self) {
+        while !self.is_over() && self.play_heuristic() {}
+    }
+
+    /// Finish the current trick the same way.
+    pub(crate) fn finish_trick(&mut self) {
+        while !self.trick.is_empty() && self.play_heuristic() {}
+    }
+
+    /// Play the card `HeuristicAgent` would for the seat whose turn it is.
+    /// False if that seat has nothing left to play.
+    fn play_heuristic(&mut self) -> bool {
+        let seat = self.turn;
+        let needed = self.bets[seat]
+            .unwrap_or(0)
+            .saturating_sub(self.tricks[seat]);
+        let legal = self.legal_cards();
+        match pick_card(legal, &self.trick, self.trump_suit, needed) {
+            Some(card) => self.play(card),
+            None => return false,
+        }
+        true
+    }
+
+    /// Points of every seat scaled to 0 (worst possible) to 1 (best possible).
+    /// The extremes are taken from betting and winning all or none of the
+    /// tricks, which covers the built in rules.
+    fn rewards(&self) -> Vec<f32> {
+        let all = self.round_num;
+        let corners = [(all, all), (0, 0), (0, all), (all, 0)];
+        let points = corners.map(|(bet, tricks)| self.scoring.score(bet, tricks) as f32);
+        let worst = points.iter().copied().fold(f32::MAX, f32::min);
+        let best = points.iter().copied().fold(f32::MIN, f32::max);
+        self.bets
+            .iter()
+            .zip(&self.tricks)
+            .map(|(bet, tricks)| {
+                let points = self.scoring.score(bet.unwrap_or(0), *tricks) as f32;
+                (points - worst) / (best - worst).max(1.0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+fn trick(plays: &[(usize, &str)]) -> Vec<Play> {
+    plays
+        .iter()
+        .map(|(seat, code)| Play {
+            card: code.parse().unwrap(),
+            seat: PlayerId(*seat),
+        })
+        .collect()
+}
+
+#[test]
+pub fn test_deal_unseen() {
+    use crate::agents::heuristic::test_view;
+    use crate::history::Trick;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Round 3 with one trick done, seat 3 didn't follow the hearts lead.
+    let mut view = test_view("AS 2C", "2D", vec![Some(1); 4]);
+    view.round_num = 3;
+    view.leader = PlayerId(0);
+    let plays = trick(&[(0, "5H"), (1, "KH"), (2, "9H"), (3, "3C")]);
+    let past_tricks = [Trick {
+        leader: PlayerId(0),
+        lead_suit: Suit::Heart,
+        winner: plays[1],
+        plays,
+    }];
+    let current = trick(&[(0, "7S")]);
+    view.past_tricks = &past_tricks;
+    view.trick = &current;
+    view.played = past_tricks[0]
+        .plays
+        .iter()
+        .chain(&current)
+        .map(|play| play.card)
+        .collect();
+
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..20 {
+        let sim = Sim::deal_unseen(&view, &CardTracker::from_view(&view), &mut rng);
+        let sizes: Vec<usize> = sim.hands.iter().map(|hand| hand.len()).collect();
+        assert_eq!(sizes, vec![1, 2, 2, 2]);
+        assert_eq!(sim.hands[1], view.hand);
+        assert!(sim.hands[3].of_suit(Suit::Heart).is_empty());
+
+        // Nobody is dealt a card that was seen.
+        let dealt = sim
+            .hands
+            .iter()
+            .fold(CardSet::EMPTY, |all, hand| all | *hand);
+        assert_eq!(dealt.len(), 7);
+        assert!((dealt & view.played).is_empty());
+        assert!(!dealt.contains("2D".parse::<Card>().unwrap().id));
+    }
+}
+
+#[test]
+pub fn test_ismcts_choices() {
+    use crate::agents::heuristic::test_view;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut agent = IsmctsAgent::new(Budget::Iterations(200));
+    let card = |code: &str| code.parse::<Card>().unwrap();
+
+    // Last to play into the trick: take it with the ace when a trick is
+    // needed, throw the deuce under it when not.
+    let mut view = test_view("AS 2S", "2H", vec![Some(1); 4]);
+    view.leader = PlayerId(2);
+    let current = trick(&[(2, "QS"), (3, "KS"), (0, "3S")]);
+    view.trick = &current;
+    view.played = current.iter().map(|play| play.card).collect();
+    view.lead_suit = Suit::Spade;
+    assert_eq!(agent.choose_card(&view, &mut rng), card("AS"));
+    view.bets = &[Some(1), Some(0), Some(1), Some(1)];
+    assert_eq!(agent.choose_card(&view, &mut rng), card("2S"));
+
+    // Two Wizards in two cards are worth two tricks.
+    let view = test_view("W W2", "2H", vec![None; 4]);
+    assert_eq!(agent.choose_bet(&view, &mut rng), 2);
+}
+
+#[test]
+pub fn test_ismcts_plays_game() {
+    use crate::config::Strategy;
+    use crate::game::{play_to_end, test_config, GameState};
+
+    let config = test_config(2)
+        .computer_with("Ismcts", Strategy::Ismcts(Budget::Iterations(20)))
+        .num_rounds(4)
+        .seed(3)
+        .build()
+        .unwrap();
+    let mut game = GameState::new(config).unwrap();
+    play_to_end(&mut game);
+    assert_eq!(game.history().len(), 4);
+}
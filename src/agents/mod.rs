@@ -0,0 +1,32 @@
+pub mod heuristic;
+pub mod ismcts;
+pub mod random;
+
+use crate::components::card::{Card, Suit};
+use crate::game::GameView;
+use core::fmt;
+use rand::RngCore;
+
+pub use heuristic::HeuristicAgent;
+pub use ismcts::IsmctsAgent;
+pub use random::RandomAgent;
+
+/// Decision maker for a seat at the table. Human input, the built in computer
+/// and third party bots all implement this and are attached to a `Player`.
+/// Any randomness should come from `rng` so seeded games can be replayed.
+pub trait PlayerAgent: fmt::Debug {
+    /// Pick the trump suit after the dealer flipped a Wizard.
+    fn choose_trump_suit(&mut self, view: &GameView, rng: &mut dyn RngCore) -> Suit;
+
+    /// Bet how many tricks will be won this round, 0 to the number of cards in hand.
+    fn choose_bet(&mut self, view: &GameView, rng: &mut dyn RngCore) -> u8;
+
+    /// Pick a card from `view.hand` to play in the current trick. Only asked
+    /// while the hand holds a card.
+    fn choose_card(&mut self, view: &GameView, rng: &mut dyn RngCore) -> Card;
+
+    /// True if the seat is played by a person in front of the terminal.
+    fn is_human(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,26 @@
+use crate::agents::PlayerAgent;
+use crate::components::card::{Card, Suit};
+use crate::components::deck::Deck;
+use crate::game::GameView;
+use crate::{get_bet_from_comp, get_play_from_comp, get_trump_from_comp};
+use rand::RngCore;
+
+/// The original computer player. Bets and picks trump at random and plays
+/// the first card that follows suit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomAgent;
+impl PlayerAgent for RandomAgent {
+    fn choose_trump_suit(&mut self, _view: &GameView, rng: &mut dyn RngCore) -> Suit {
+        get_trump_from_comp(rng)
+    }
+
+    fn choose_bet(&mut self, view: &GameView, rng: &mut dyn RngCore) -> u8 {
+        get_bet_from_comp(view.legal_bets, rng)
+    }
+
+    fn choose_card(&mut self, view: &GameView, _rng: &mut dyn RngCore) -> Card {
+        let hand = Deck::from(view.hand);
+        let index = get_play_from_comp(&hand, view.lead_suit);
+        hand[index.expect("asked to play from an empty hand")]
+    }
+}
@@ -0,0 +1,103 @@
+use wizard::config::{Difficulty, Scoring};
+use wizard::error::WizardError;
+
+pub const USAGE: &str = "Usage: wizard-cli [OPTIONS]
+
+Without --players, --humans, --names or --bots-only the table is set up interactively.
+
+Options:
+  --players <3-6>      Number of seats at the table (default 6)
+  --humans <n>         How many of the seats are played at this terminal (default 1)
+  --names <a,b,..>     Comma separated names, humans first then computers
+  --bots-only          Every seat is a computer, the game plays itself
+  --difficulty <lvl>   novice, intermediate or expert for every computer, or a comma
+                       separated list with one per computer (default intermediate)
+  --seed <n>           Replay the game dealt from this seed
+  --rounds <n>         Stop after this many rounds
+  --scoring <rule>     official, classic or zero-bonus[=points] (default official)
+  --uneven-bets        Bets may not add up to the number of cards dealt
+  --no-delay           Don't pause between computer moves
+  --save <file>        Where the game is saved after every trick (default wizard-save.json)
+  --resume <file>      Pick up a saved game, the options above are ignored
+  --help               Show this message";
+
+/// Options given on the command line.
+#[derive(Debug, Default)]
+pub struct Args {
+    pub players: Option<usize>,
+    pub humans: Option<usize>,
+    pub names: Vec<String>,
+    pub bots_only: bool,
+    pub difficulty: Vec<Difficulty>,
+    pub seed: Option<u64>,
+    pub rounds: Option<usize>,
+    pub scoring: Option<Scoring>,
+    pub uneven_bets: bool,
+    pub no_delay: bool,
+    pub save: Option<String>,
+    pub resume: Option<String>,
+    pub help: bool,
+}
+impl Args {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+        let mut parsed = Args::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--players" => parsed.players = Some(parse_value(&arg, args.next())?),
+                "--humans" => parsed.humans = Some(parse_value(&arg, args.next())?),
+                "--names" => {
+                    let names: String = parse_value(&arg, args.next())?;
+                    parsed.names = names
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .collect();
+                }
+                "--bots-only" => parsed.bots_only = true,
+                "--difficulty" => {
+                    let levels: String = parse_value(&arg, args.next())?;
+                    parsed.difficulty = levels
+                        .split(',')
+                        .map(|level| level.parse().map_err(|err: WizardError| err.to_string()))
+                        .collect::<Result<_, String>>()?;
+                }
+                "--seed" => parsed.seed = Some(parse_value(&arg, args.next())?),
+                "--rounds" => parsed.rounds = Some(parse_value(&arg, args.next())?),
+                "--scoring" => parsed.scoring = Some(parse_value(&arg, args.next())?),
+                "--uneven-bets" => parsed.uneven_bets = true,
+                "--no-delay" => parsed.no_delay = true,
+                "--save" => parsed.save = Some(parse_value(&arg, args.next())?),
+                "--resume" => parsed.resume = Some(parse_value(&arg, args.next())?),
+                "--help" | "-h" => parsed.help = true,
+                _ => return Err(format!("Unknown option {}", arg)),
+            }
+        }
+
+        if parsed.bots_only && parsed.humans.unwrap_or(0) > 0 {
+            return Err(String::from("--bots-only can't be used with --humans"));
+        }
+        Ok(parsed)
+    }
+
+    /// Difficulty of the `computer`th computer seat, counting from 0.
+    pub fn difficulty(&self, computer: usize) -> Difficulty {
+        match &self.difficulty[..] {
+            [level] => *level,
+            levels => levels.get(computer).copied().unwrap_or_default(),
+        }
+    }
+
+    /// True if any option describing the seats was given.
+    pub fn has_roster(&self) -> bool {
+        self.players.is_some() || self.humans.is_some() || !self.names.is_empty() || self.bots_only
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("Can't understand {} {}", flag, value)),
+        None => Err(format!("{} needs a value", flag)),
+    }
+}
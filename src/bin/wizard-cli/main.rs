@@ -0,0 +1,543 @@
+mod args;
+
+use args::{Args, USAGE};
+use core::time;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::path::Path;
+use std::{env, fs, process};
+use wizard::advisor::Advisor;
+use wizard::agents::PlayerAgent;
+use wizard::components::card::{Card, Rank, Suit};
+use wizard::components::card_set::CardSet;
+use wizard::components::deck::Deck;
+use wizard::components::player::*;
+use wizard::components::util::Util;
+use wizard::config::{Difficulty, GameConfig, PlayerConfig, PlayerKind, RuleVariants, Strategy};
+use wizard::error::WizardError;
+use wizard::events::{Event, GameObserver};
+use wizard::game::{Action, GameState, GameView, Phase};
+use wizard::save::SavedGame;
+use wizard::table::PlayerId;
+use wizard::{legal_cards, MAX_PLAYERS, MIN_PLAYERS};
+
+const COMPUTER_NAMES: [&str; 6] = ["Merlin", "Oz", "Sarumon", "Gandalf", "Kvothe", "Radagast"];
+const DEFAULT_SAVE_FILE: &str = "wizard-save.json";
+
+fn main() {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => exit_with(&format!("{}\n\n{}", message, USAGE)),
+    };
+    if args.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    Util::print_wizard_ascii_art();
+
+    // Keep saving to the file a game was resumed from unless told otherwise.
+    let save_file = match (&args.save, &args.resume) {
+        (Some(path), _) | (None, Some(path)) => path.as_str(),
+        (None, None) => DEFAULT_SAVE_FILE,
+    };
+    let save_path = Path::new(save_file);
+
+    let mut game = match &args.resume {
+        Some(path) => {
+            println!("Resuming game from {}\n", path);
+            or_exit(SavedGame::read_from(Path::new(path)).and_then(GameState::resume))
+        }
+        None => {
+            let seed = args.seed.unwrap_or_else(|| rand::thread_rng().gen());
+            println!("Seed: {} (replay this game with --seed {})\n", seed, seed);
+
+            match get_config(&args, seed) {
+                Ok(config) => or_exit(GameState::new(config)),
+                Err(message) => exit_with(&message),
+            }
+        }
+    };
+    attach_humans(&mut game);
+    let names = game.players().iter().map(|player| player.name.clone());
+    game.add_observer(Box::new(TablePrinter {
+        names: names.collect(),
+    }));
+    Player::print_names(game.players());
+
+    println!("\nThe game is saved to {} after every trick.", save_file);
+    let verb = match args.resume {
+        Some(_) => "continue",
+        None => "start first round",
+    };
+    press_enter_to_(&game, verb);
+
+    while game.phase() != Phase::GameOver {
+        // A resumed game can pick up in the middle of a round.
+        if game.phase() == Phase::Dealing {
+            or_exit(game.deal());
+        }
+
+        println!(
+            "\n--- Round {:>2} --- \nDealer: {} \nLeader: {}",
+            game.round_num(),
+            game.player(game.dealer()).name,
+            game.player(game.leader()).name
+        );
+        let mut humans = game
+            .players()
+            .iter()
+            .filter(|player| player.agent.is_human());
+        if let (Some(player), None) = (humans.next(), humans.next()) {
+            println!(
+                "\nYour hand: {}\n",
+                sorted_hand(player.hand, game.trump_suit())
+            );
+        }
+
+        match game.trump() {
+            Some(card) => set_trump(&mut game, card),
+            None => println!("No Trump!"),
+        };
+        println!("--------------------");
+
+        if game.phase() == Phase::Betting {
+            press_enter_to_(&game, "start betting");
+
+            place_bets(&mut game);
+            press_enter_to_(&game, "play first trick");
+        }
+
+        play_tricks(&mut game, save_path);
+
+        println!("End of round #{} - Current standings:", game.round_num());
+        print_score(&game);
+
+        press_enter_to_(&game, "start next round");
+    }
+
+    if let Some(winner) = game.winner() {
+        println!(
+            "{} is the winner with {} points!",
+            game.player(winner).name,
+            game.scores()[winner.index()]
+        );
+    }
+
+    // Nothing left to resume.
+    let _ = fs::remove_file(save_path);
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+/// Unwrap `result` or quit with its error, e.g. when stdin is closed mid game.
+fn or_exit<T>(result: Result<T, WizardError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => exit_with(&err.to_string()),
+    }
+}
+
+/// Wait for the people at the table, bots don't need a break.
+fn press_enter_to_(game: &GameState, verb: &str) {
+    if game.players().iter().any(|player| player.agent.is_human()) {
+        or_exit(Util::press_enter_to_(verb));
+    }
+}
+
+fn get_config(args: &Args, seed: u64) -> Result<GameConfig, String> {
+    let players = if args.has_roster() {
+        get_players_from_args(args)?
+    } else {
+        or_exit(get_players(args))
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut config = GameConfig::builder()
+        .players(Util::shuffle_vec(players, &mut rng))
+        .seed(seed)
+        .variants(RuleVariants {
+            uneven_bets: args.uneven_bets,
+        });
+    if let Some(rounds) = args.rounds {
+        config = config.num_rounds(rounds);
+    }
+    if let Some(scoring) = args.scoring {
+        config = config.scoring(scoring);
+    }
+    if args.no_delay {
+        config = config.delay(time::Duration::from_millis(0));
+    }
+    config.build().map_err(|err| err.to_string())
+}
+
+fn get_players_from_args(args: &Args) -> Result<Vec<PlayerConfig>, String> {
+    let num_humans = if args.bots_only {
+        0
+    } else {
+        args.humans.unwrap_or(1)
+    };
+    let num_players = match (args.players, args.names.len()) {
+        (Some(num_players), _) => num_players,
+        (None, 0) => COMPUTER_NAMES.len(),
+        (None, num_names) => num_names,
+    };
+
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&num_players) {
+        return Err(WizardError::PlayerCount(num_players).to_string());
+    }
+    if num_humans > num_players {
+        return Err(format!(
+            "Can't seat {} humans at a table of {}",
+            num_humans, num_players
+        ));
+    }
+    if args.names.len() > num_players {
+        return Err(format!(
+            "Got {} names for {} players",
+            args.names.len(),
+            num_players
+        ));
+    }
+    let num_computers = num_players - num_humans;
+    if args.difficulty.len() > 1 && args.difficulty.len() != num_computers {
+        return Err(format!(
+            "Got {} difficulties for {} computers",
+            args.difficulty.len(),
+            num_computers
+        ));
+    }
+
+    // Seats past the names given: humans are numbered, computers get a
+    // default name nobody took with --names.
+    let mut names = args.names.clone();
+    names.extend((names.len()..num_humans).map(|seat| format!("Player {}", seat + 1)));
+    let untaken = COMPUTER_NAMES
+        .iter()
+        .filter(|name| !args.names.iter().any(|taken| taken == *name));
+    let unnamed = num_players - names.len();
+    names.extend(untaken.take(unnamed).map(|name| String::from(*name)));
+
+    let players = names
+        .into_iter()
+        .enumerate()
+        .map(|(seat, name)| {
+            let kind = if seat < num_humans {
+                PlayerKind::Human
+            } else {
+                PlayerKind::Computer
+            };
+            let strategy = match kind {
+                PlayerKind::Human => Strategy::default(),
+                PlayerKind::Computer => args.difficulty(seat - num_humans).strategy(),
+            };
+            PlayerConfig {
+                name,
+                kind,
+                strategy,
+            }
+        })
+        .collect();
+    Ok(players)
+}
+
+fn get_players(args: &Args) -> Result<Vec<PlayerConfig>, WizardError> {
+    println!("How many players? ({}-{})", MIN_PLAYERS, MAX_PLAYERS);
+    let num_players = loop {
+        let num = Util::cli_next_num()? as usize;
+        if (MIN_PLAYERS..=MAX_PLAYERS).contains(&num) {
+            break num;
+        }
+        println!("Hey! {}.", WizardError::PlayerCount(num));
+    };
+
+    let mut computer_names = COMPUTER_NAMES.iter();
+    let mut players = Vec::new();
+    let mut computers = 0;
+    for seat in 1..(num_players + 1) {
+        // First seat defaults to the person at the terminal, the rest to computers.
+        let default = if seat == 1 { "h" } else { "c" };
+        println!("\nIs player {} (h)uman or (c)omputer? [{}]", seat, default);
+        let kind = loop {
+            match Util::cli_next_string_or(default)?.to_lowercase().as_str() {
+                "h" | "human" => break PlayerKind::Human,
+                "c" | "computer" => break PlayerKind::Computer,
+                _ => println!("Hey! Gotta pick h or c!"),
+            }
+        };
+
+        let default_name = match kind {
+            PlayerKind::Human => format!("Player {}", seat),
+            PlayerKind::Computer => String::from(*computer_names.next().unwrap()),
+        };
+        println!("Name? [{}]", default_name);
+        let name = Util::cli_next_string_or(&default_name)?;
+
+        let strategy = match kind {
+            PlayerKind::Human => Strategy::default(),
+            PlayerKind::Computer => get_difficulty(args.difficulty(computers))?.strategy(),
+        };
+        if kind == PlayerKind::Computer {
+            computers += 1;
+        }
+        players.push(PlayerConfig {
+            name,
+            kind,
+            strategy,
+        });
+    }
+
+    Ok(players)
+}
+
+fn get_difficulty(default: Difficulty) -> Result<Difficulty, WizardError> {
+    println!("(n)ovice, (i)ntermediate or (e)xpert? [{}]", default);
+    loop {
+        match Util::cli_next_string_or(default.name())?.parse() {
+            Ok(level) => return Ok(level),
+            Err(_) => println!("Hey! Gotta pick n, i or e!"),
+        }
+    }
+}
+
+/// Hand the human seats over to the terminal.
+fn attach_humans(game: &mut GameState) {
+    let humans: Vec<(usize, String)> = game
+        .config()
+        .players
+        .iter()
+        .enumerate()
+        .filter(|(_, player)| player.kind == PlayerKind::Human)
+        .map(|(seat, player)| (seat, player.name.clone()))
+        .collect();
+
+    // Only show whose turn it is when several people share the terminal.
+    let show_name = humans.len() > 1;
+    for (seat, name) in humans {
+        game.set_agent(PlayerId(seat), Box::new(HumanAgent { name, show_name }));
+    }
+}
+
+fn set_trump(game: &mut GameState, card: Card) {
+    println!(" Trump: {}", card);
+
+    if game.phase() != Phase::ChoosingTrump {
+        // Resumed after the dealer already picked a suit for the Wizard.
+        if card.rank == Rank::Wizard && card.suit != Suit::Suitless {
+            println!("\n Trump suit: {}", card.suit.symbol());
+        }
+        return;
+    }
+
+    println!("\nTrump is a Wizard!");
+    let dealer = game.player(game.dealer());
+    if !dealer.agent.is_human() {
+        println!("{} will select suit...", dealer.name);
+        Util::sleep(game.config().delay);
+    }
+    or_exit(game.step());
+}
+
+fn place_bets(game: &mut GameState) {
+    while game.phase() == Phase::Betting {
+        let index = game.turn();
+        if !game.player(index).agent.is_human() {
+            Util::sleep(game.config().delay);
+        }
+        or_exit(game.step());
+    }
+}
+
+fn play_tricks(game: &mut GameState, save_path: &Path) {
+    while game.phase() == Phase::Playing {
+        let tricks_played = game.history().last().map_or(0, |round| round.tricks.len());
+        println!("======= Trick #{} =======", tricks_played + 1);
+
+        loop {
+            if !game.player(game.turn()).agent.is_human() {
+                Util::sleep(game.config().delay);
+            }
+            if let Action::Played(_, Some(_)) = or_exit(game.step()) {
+                break;
+            }
+        }
+
+        // Losing the save shouldn't end the game.
+        if let Err(err) = game.save().write_to(save_path) {
+            eprintln!("{}", err);
+        }
+        press_enter_to_(game, "play next trick");
+    }
+}
+
+/// Prints the moves at the table as they happen.
+#[derive(Debug)]
+struct TablePrinter {
+    names: Vec<String>,
+}
+impl GameObserver for TablePrinter {
+    fn notify(&mut self, event: &Event) {
+        match event {
+            Event::TrumpSuitChosen { suit, .. } => println!("\n Trump suit: {}", suit.symbol()),
+            Event::BetPlaced { seat, bet } => {
+                println!("{:>8} bet {}", self.names[seat.index()], bet)
+            }
+            Event::CardPlayed(play) => {
+                println!("{:>8}: {}", self.names[play.seat.index()], play.card)
+            }
+            Event::TrickWon(play) => println!(
+                "\n  Winner: {} - {}\n========================",
+                play.card,
+                self.names[play.seat.index()]
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Asks the person at the terminal for every decision.
+#[derive(Debug)]
+struct HumanAgent {
+    name: String,
+    show_name: bool,
+}
+impl HumanAgent {
+    fn announce(&self) {
+        if self.show_name {
+            println!("\n--- {}'s turn ---", self.name);
+        }
+    }
+}
+impl PlayerAgent for HumanAgent {
+    fn choose_trump_suit(&mut self, _view: &GameView, _rng: &mut dyn RngCore) -> Suit {
+        self.announce();
+        let suits = Suit::ALL;
+        println!("Which suit do you select as trump?");
+        for (i, suit) in suits.iter().enumerate() {
+            println!("  {}. {}", i + 1, suit.symbol());
+        }
+
+        suits[or_exit(Util::cli_next_index(suits.len()))]
+    }
+
+    fn choose_bet(&mut self, view: &GameView, _rng: &mut dyn RngCore) -> u8 {
+        let max_bet = view.hand.len() as u8;
+        self.announce();
+        if self.show_name {
+            println!("Your hand: {}", sorted_hand(view.hand, view.trump_suit()));
+        }
+        println!("What is your bet? (? for a hint)");
+
+        loop {
+            let bet = match or_exit(Util::cli_next_num_or_hint()) {
+                Some(bet) => bet,
+                None => {
+                    print_bet_hint(view);
+                    continue;
+                }
+            };
+            if bet > max_bet {
+                println!("{}", WizardError::BetTooHigh { bet, max_bet });
+                continue;
+            }
+            if !view.legal_bets.contains(&bet) {
+                println!("{}", WizardError::EvenBets(bet));
+                continue;
+            }
+            return bet;
+        }
+    }
+
+    fn choose_card(&mut self, view: &GameView, _rng: &mut dyn RngCore) -> Card {
+        self.announce();
+        get_play_from_human(view)
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+}
+
+fn print_score(game: &GameState) {
+    println!("\n Name    Score   Bet   Tricks");
+    println!(" ----------------------------");
+    for seat in game.table().seats() {
+        println!(
+            " {:8} {:>4}   {:>2}     {:>2}",
+            game.player(seat).name,
+            game.scores()[seat.index()],
+            game.bets()[seat.index()].unwrap_or(0),
+            game.tricks()[seat.index()]
+        );
+    }
+}
+
+/// Advisor's bet for the hand, with the chance of each number of tricks.
+fn print_bet_hint(view: &GameView) {
+    let advice = Advisor::default().advise_bet(view, &mut rand::thread_rng());
+    println!("\n Advisor suggests a bet of {}.", advice.bet);
+    print!(" Tricks you'd take betting {}:", advice.bet);
+    for (tricks, chance) in advice.tricks.iter().enumerate() {
+        print!("  {}: {:.0}%", tricks, chance * 100.0);
+    }
+    println!("\n");
+}
+
+/// Advisor's ranking of the legal cards, numbered as in `hand`.
+fn print_card_hint(view: &GameView, hand: &[Card]) {
+    let advice = Advisor::default().advise_card(view, &mut rand::thread_rng());
+    println!("\n Advisor, best first:");
+    for card in advice {
+        let index = hand.iter().position(|c| c.id == card.card.id).unwrap();
+        println!(
+            "  {}. {}  wins the trick {:>3.0}%, makes your bet {:>3.0}%",
+            index + 1,
+            card.card,
+            card.win_trick * 100.0,
+            card.make_bet * 100.0
+        );
+    }
+    println!();
+}
+
+fn sorted_hand(hand: CardSet, trump_suit: Suit) -> Deck {
+    let mut hand = Deck::from(hand);
+    hand.sort_for_display(trump_suit);
+    hand
+}
+
+fn get_play_from_human(view: &GameView) -> Card {
+    let hand = sorted_hand(view.hand, view.trump_suit());
+    let legal = legal_cards(&hand, view.lead_suit);
+
+    // Cards that can't be played this trick are shown in brackets.
+    println!("\nYour hand:");
+    for (index, card) in hand.iter().enumerate() {
+        if legal.contains(card) {
+            println!("  {}. {}", index + 1, card);
+        } else {
+            println!("  {}. ({})", index + 1, card);
+        }
+    }
+
+    println!("Which card will you play? (? for a hint)");
+
+    loop {
+        let card = match or_exit(Util::cli_next_index_or_hint(hand.len())) {
+            Some(index) => hand[index],
+            None => {
+                print_card_hint(view, &hand);
+                continue;
+            }
+        };
+        if !legal.contains(&card) {
+            println!("{}", WizardError::MustFollowSuit(view.lead_suit));
+            continue;
+        }
+
+        return card;
+    }
+}
@@ -1,6 +1,10 @@
-use core::fmt;
+use crate::error::WizardError;
+use core::{cmp, fmt, hash, str};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Suits are ordered as declared, which only matters for sorting.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Suit {
     Club,
     Diamond,
@@ -9,6 +13,9 @@ pub enum Suit {
     Suitless,
 }
 impl Suit {
+    /// The four suits a card can have, Suitless is left out.
+    pub const ALL: [Suit; 4] = [Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade];
+
     pub fn symbol(self) -> char {
         match self {
             Suit::Club => '♣',
@@ -18,9 +25,21 @@ impl Suit {
             Suit::Suitless => ' ',
         }
     }
+
+    /// ASCII stand in for `symbol`, `-` for Suitless.
+    pub fn letter(self) -> char {
+        match self {
+            Suit::Club => 'C',
+            Suit::Diamond => 'D',
+            Suit::Heart => 'H',
+            Suit::Spade => 'S',
+            Suit::Suitless => '-',
+        }
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Ranks are ordered by `value`: Jester lowest, then Two to Ace, Wizard highest.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Rank {
     Two,
     Three,
@@ -39,6 +58,25 @@ pub enum Rank {
     Jester,
 }
 impl Rank {
+    /// Every rank from low to high, then Wizard and Jester.
+    pub const ALL: [Rank; 15] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+        Rank::Wizard,
+        Rank::Jester,
+    ];
+
     pub fn symbol(self) -> &'static str {
         match self {
             Rank::Two => "2",
@@ -78,14 +116,383 @@ impl Rank {
         }
     }
 }
+impl Ord for Rank {
+    fn cmp(&self, other: &Rank) -> cmp::Ordering {
+        self.value().cmp(&other.value())
+    }
+}
+impl PartialOrd for Rank {
+    fn partial_cmp(&self, other: &Rank) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl str::FromStr for Suit {
+    type Err = WizardError;
+
+    /// Parses a letter (`H`, `s`), symbol (`♥`) or name (`Hearts`).
+    /// `-` is Suitless.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let suit = match s.trim().to_lowercase().as_str() {
+            "c" | "♣" | "club" | "clubs" => Suit::Club,
+            "d" | "♦" | "diamond" | "diamonds" => Suit::Diamond,
+            "h" | "♥" | "heart" | "hearts" => Suit::Heart,
+            "s" | "♠" | "spade" | "spades" => Suit::Spade,
+            "-" => Suit::Suitless,
+            _ => return Err(WizardError::Parse(format!("suit {:?}", s))),
+        };
+        Ok(suit)
+    }
+}
+
+impl str::FromStr for Rank {
+    type Err = WizardError;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    /// Parses a symbol as shown on cards, in any case: `2` to `10`, `J`, `Q`,
+    /// `K`, `A`, `W` and `Je`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let symbol = s.trim();
+        Rank::ALL
+            .iter()
+            .find(|rank| rank.symbol().eq_ignore_ascii_case(symbol))
+            .copied()
+            .ok_or_else(|| WizardError::Parse(format!("rank {:?}", s)))
+    }
+}
+
+/// Identity of one of the 60 physical cards in the deck, so the four Wizards
+/// and four Jesters can be told apart. 0 to 51 are the numbered cards by suit
+/// then rank (2♣ is 0, A♠ is 51), 52 to 55 the Wizards and 56 to 59 the Jesters.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CardId(u8);
+impl CardId {
+    pub const COUNT: usize = 60;
+
+    pub fn new(id: u8) -> Option<CardId> {
+        if (id as usize) < CardId::COUNT {
+            Some(CardId(id))
+        } else {
+            None
+        }
+    }
+
+    /// Every card id from 0 to 59.
+    pub fn all() -> impl Iterator<Item = CardId> {
+        (0..CardId::COUNT as u8).map(CardId)
+    }
+
+    /// Id of the first copy of `rank`, in `suit` for numbered cards.
+    const fn first_of(rank: Rank, suit: Suit) -> CardId {
+        match (rank, suit) {
+            (Rank::Wizard, _) => CardId(52),
+            (Rank::Jester, _) => CardId(56),
+            (_, Suit::Suitless) => panic!("numbered cards need a suit"),
+            _ => CardId(suit as u8 * 13 + rank as u8),
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Which of the four Wizards or Jesters this is, 1 to 4. Always 1 for
+    /// numbered cards.
+    pub fn copy(self) -> u8 {
+        match self.0 {
+            52..=59 => (self.0 - 52) % 4 + 1,
+            _ => 1,
+        }
+    }
+
+    pub fn card(self) -> Card {
+        let (rank, suit) = match self.0 {
+            52..=55 => (Rank::Wizard, Suit::Suitless),
+            56..=59 => (Rank::Jester, Suit::Suitless),
+            id => (Rank::ALL[id as usize % 13], Suit::ALL[id as usize / 13]),
+        };
+        Card {
+            rank,
+            suit,
+            id: self,
+        }
+    }
+}
+impl From<CardId> for Card {
+    fn from(id: CardId) -> Self {
+        id.card()
+    }
+}
+
+/// A card is compared by rank and suit only, so all four Wizards are equal.
+/// Compare `id`s to tell the physical cards apart.
+#[derive(Clone, Copy, Debug)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
+    pub id: CardId,
+}
+impl Card {
+    /// The card of `rank` and `suit`. Wizards and Jesters are the first of
+    /// their four copies, use `CardId::card` for the others.
+    ///
+    /// # Panics
+    ///
+    /// If `rank` is numbered and `suit` is Suitless, there is no such card.
+    pub const fn new(rank: Rank, suit: Suit) -> Card {
+        Card {
+            rank,
+            suit,
+            id: CardId::first_of(rank, suit),
+        }
+    }
+
+    /// Short ASCII form such as `10H` or `QS`. Wizards and Jesters have no
+    /// suit so they are just `W` and `Je`, unless a flipped Wizard was given
+    /// the trump suit (`WC`). Their other copies are numbered: `W2`, `Je4`.
+    pub fn code(&self) -> String {
+        let mut code = String::from(self.rank.symbol());
+        if self.id.copy() > 1 {
+            code.push_str(&self.id.copy().to_string());
+        }
+        if self.suit != Suit::Suitless {
+            code.push(self.suit.letter());
+        }
+        code
+    }
+}
+impl PartialEq for Card {
+    fn eq(&self, other: &Card) -> bool {
+        self.rank == other.rank && self.suit == other.suit
+    }
+}
+impl Eq for Card {}
+/// By suit then rank, ignoring trump. Like equality, copies of a card tie.
+impl Ord for Card {
+    fn cmp(&self, other: &Card) -> cmp::Ordering {
+        (self.suit, self.rank).cmp(&(other.suit, other.rank))
+    }
+}
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Card) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
+impl hash::Hash for Card {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.rank.hash(state);
+        self.suit.hash(state);
+    }
+}
+impl str::FromStr for Card {
+    type Err = WizardError;
+
+    /// Parses a rank followed by a suit, such as `10h`, `QS` or `10♥`. Wizards
+    /// and Jesters are just `W` and `Je`. A flipped Wizard may carry the trump
+    /// suit (`WC`), a Jester never has one.
+    /// Reads back both `code` and `Display` output.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || WizardError::Parse(format!("card {:?}", s));
+        let text = s.trim();
+
+        // Suit comes last, but not every card has one.
+        let (body, suit) = match text.char_indices().last() {
+            Some((index, _)) => match text[index..].parse() {
+                Ok(Suit::Suitless) | Err(_) => (text, Suit::Suitless),
+                Ok(suit) => (&text[..index], suit),
+            },
+            None => return Err(error()),
+        };
+
+        // Wizards and Jesters can be followed by which copy they are.
+        let (rank, copy) = match body.parse::<Rank>() {
+            Ok(rank) => (rank, 1),
+            Err(_) => {
+                let split = body.char_indices().last().map_or(0, |(index, _)| index);
+                let (rank, copy) = body.split_at(split);
+                match (rank.parse(), copy.parse::<u8>()) {
+                    (Ok(rank @ Rank::Wizard), Ok(copy @ 1..=4))
+                    | (Ok(rank @ Rank::Jester), Ok(copy @ 1..=4)) => (rank, copy),
+                    _ => return Err(error()),
+                }
+            }
+        };
+
+        match rank {
+            Rank::Jester if suit != Suit::Suitless => Err(error()),
+            Rank::Wizard | Rank::Jester => {
+                let card = Card::new(rank, suit);
+                Ok(Card {
+                    id: CardId(card.id.0 + copy - 1),
+                    ..card
+                })
+            }
+            _ if suit == Suit::Suitless => {
+                Err(WizardError::Parse(format!("card {:?} needs a suit", s)))
+            }
+            _ => Ok(Card::new(rank, suit)),
+        }
+    }
+}
+
+#[test]
+pub fn test_parse_card() {
+    let card = Card::new;
+    assert_eq!("10h".parse(), Ok(card(Rank::Ten, Suit::Heart)));
+    assert_eq!("QS".parse(), Ok(card(Rank::Queen, Suit::Spade)));
+    assert_eq!("a♦".parse(), Ok(card(Rank::Ace, Suit::Diamond)));
+    assert_eq!("W".parse(), Ok(crate::WIZARD));
+    assert_eq!("je".parse(), Ok(crate::JESTER));
+    assert_eq!("JC".parse(), Ok(card(Rank::Jack, Suit::Club)));
+    assert_eq!("WC".parse(), Ok(card(Rank::Wizard, Suit::Club)));
+
+    assert!("10".parse::<Card>().is_err());
+    assert!("1H".parse::<Card>().is_err());
+    assert!("KX".parse::<Card>().is_err());
+    assert!("".parse::<Card>().is_err());
+
+    // Display and code both read back.
+    for rank in Rank::ALL.iter().take(13) {
+        for suit in Suit::ALL {
+            let card = card(*rank, suit);
+            assert_eq!(card.to_string().parse(), Ok(card));
+            assert_eq!(card.code().parse(), Ok(card));
+        }
+    }
+    assert_eq!(crate::WIZARD.to_string().parse(), Ok(crate::WIZARD));
+    assert_eq!(crate::JESTER.to_string().parse(), Ok(crate::JESTER));
+
+    // Copies of Wizards and Jesters keep their identity.
+    let third_jester: Card = "Je3".parse().unwrap();
+    assert_eq!(third_jester, crate::JESTER);
+    assert_eq!(third_jester.id, CardId::new(58).unwrap());
+    assert_eq!(third_jester.code(), "Je3");
+    assert_eq!("w4c".parse::<Card>().unwrap().id.copy(), 4);
+    assert!("W5".parse::<Card>().is_err());
+    assert!("K2H".parse::<Card>().is_err());
+    assert!("♥♥".parse::<Card>().is_err());
+    assert!("JeH".parse::<Card>().is_err());
+    assert!("Je2S".parse::<Card>().is_err());
+
+    assert_eq!("Hearts".parse(), Ok(Suit::Heart));
+    assert_eq!("-".parse(), Ok(Suit::Suitless));
+}
+
+#[test]
+pub fn test_card_order() {
+    assert!(Rank::Jester < Rank::Two);
+    assert!(Rank::Ace < Rank::Wizard);
+    assert!(Suit::Club < Suit::Spade);
+    let card = |s: &str| s.parse::<Card>().unwrap();
+    assert!(card("AC") < card("2D"));
+    assert!(card("2D") < card("3D"));
+    assert_eq!(card("W").cmp(&card("W3")), cmp::Ordering::Equal);
+}
+
+#[test]
+pub fn test_card_ids() {
+    let ids: Vec<CardId> = CardId::all().collect();
+    assert_eq!(ids.len(), CardId::COUNT);
+    for id in ids {
+        assert_eq!(id.card().id, id);
+        // Every card but the extra Wizards and Jesters has the id `new` gives it.
+        if id.copy() == 1 {
+            assert_eq!(Card::new(id.card().rank, id.card().suit).id, id);
+        }
+    }
+
+    assert_eq!(Card::new(Rank::Two, Suit::Club).id.index(), 0);
+    assert_eq!(Card::new(Rank::Ace, Suit::Spade).id.index(), 51);
+    assert_eq!(crate::WIZARD.id.index(), 52);
+    assert_eq!(crate::JESTER.id.index(), 56);
+    assert_eq!(CardId::new(60), None);
+    assert!(std::panic::catch_unwind(|| Card::new(Rank::Ace, Suit::Suitless)).is_err());
+
+    // Same value, different physical card.
+    let second_wizard = CardId::new(53).unwrap().card();
+    assert_eq!(second_wizard, crate::WIZARD);
+    assert_ne!(second_wizard.id, crate::WIZARD.id);
+}
+
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:>2}{}", self.rank.symbol(), self.suit.symbol())
     }
 }
+
+// Saved in the short text form so save files stay readable, e.g.
+// `["10H", "W", "Je"]` rather than nested objects.
+#[cfg(feature = "serde")]
+impl Serialize for Suit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.letter().to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Suit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Rank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.symbol())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn test_serde_cards() {
+    use crate::components::deck::Deck;
+
+    let hand = Deck(vec![
+        Card::new(Rank::Ten, Suit::Heart),
+        Card::new(Rank::Queen, Suit::Spade),
+        crate::WIZARD,
+        crate::JESTER,
+        Card::new(Rank::Wizard, Suit::Club),
+    ]);
+    let json = serde_json::to_string(&hand).unwrap();
+    assert_eq!(json, r#"["10H","QS","W","Je","WC"]"#);
+    assert_eq!(serde_json::from_str::<Deck>(&json).unwrap(), hand);
+
+    // Every card in the deck survives the trip, copies of Wizards too.
+    let deck = Deck::build();
+    let json = serde_json::to_string(&deck).unwrap();
+    let ids = |deck: &Deck| deck.iter().map(|card| card.id).collect::<Vec<_>>();
+    assert_eq!(ids(&serde_json::from_str(&json).unwrap()), ids(&deck));
+
+    assert_eq!(serde_json::to_string(&Suit::Suitless).unwrap(), r#""-""#);
+    assert!(serde_json::from_str::<Card>(r#""1H""#).is_err());
+    assert!(serde_json::from_str::<Card>(r#""KX""#).is_err());
+
+    // Anything `FromStr` understands is read, only the short form is written.
+    assert_eq!(serde_json::from_str::<Card>(r#""10♥""#).unwrap(), hand[0]);
+    assert!(serde_json::from_str::<Card>(r#""""#).is_err());
+}
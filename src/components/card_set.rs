@@ -0,0 +1,272 @@
+use crate::components::card::{Card, CardId, Rank, Suit};
+use crate::components::deck::Deck;
+use core::{fmt, iter, ops};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Set of physical cards stored as one bit per `CardId`. Copying, set
+/// operations and suit lookups are a few instructions with no allocation,
+/// which is what simulations playing many games need.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize, Serialize),
+    serde(from = "Deck", into = "Deck")
+)]
+pub struct CardSet(u64);
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+    /// All 60 cards.
+    pub const ALL: CardSet = CardSet((1 << CardId::COUNT) - 1);
+    pub const WIZARDS: CardSet = CardSet(0b1111 << 52);
+    pub const JESTERS: CardSet = CardSet(0b1111 << 56);
+
+    /// The 13 numbered cards of `suit`, empty for Suitless.
+    pub fn suit(suit: Suit) -> CardSet {
+        match suit {
+            Suit::Suitless => CardSet::EMPTY,
+            suit => CardSet(0x1fff << (suit as u64 * 13)),
+        }
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, id: CardId) -> bool {
+        self.0 & bit(id) != 0
+    }
+
+    /// Returns false if the card was already in the set.
+    pub fn insert(&mut self, id: CardId) -> bool {
+        let added = !self.contains(id);
+        self.0 |= bit(id);
+        added
+    }
+
+    /// Returns false if the card wasn't in the set.
+    pub fn remove(&mut self, id: CardId) -> bool {
+        let removed = self.contains(id);
+        self.0 &= !bit(id);
+        removed
+    }
+
+    /// Cards of `suit` in the set, Wizards and Jesters not included.
+    pub fn of_suit(self, suit: Suit) -> CardSet {
+        self & CardSet::suit(suit)
+    }
+
+    /// Card ids from low to high.
+    pub fn iter(self) -> Iter {
+        Iter(self.0)
+    }
+
+    /// Cards from low to high id. Wizards come back Suitless.
+    pub fn cards(self) -> impl Iterator<Item = Card> {
+        self.iter().map(CardId::card)
+    }
+
+    /// The highest numbered card of `suit` in the set.
+    pub fn highest_of_suit(self, suit: Suit) -> Option<Card> {
+        let cards = self.of_suit(suit).0;
+        if cards == 0 {
+            return None;
+        }
+        let id = 63 - cards.leading_zeros() as u8;
+        CardId::new(id).map(CardId::card)
+    }
+
+    /// The lowest numbered card of `suit` in the set.
+    pub fn lowest_of_suit(self, suit: Suit) -> Option<Card> {
+        self.of_suit(suit).iter().next().map(CardId::card)
+    }
+
+    /// How many cards in the set outrank `card` within its suit.
+    pub fn count_above(self, card: Card) -> usize {
+        match card.rank {
+            Rank::Wizard | Rank::Jester => 0,
+            _ => {
+                let above = !((bit(card.id) << 1) - 1);
+                (self.of_suit(card.suit).0 & above).count_ones() as usize
+            }
+        }
+    }
+}
+
+fn bit(id: CardId) -> u64 {
+    1 << id.index()
+}
+
+impl ops::BitOr for CardSet {
+    type Output = CardSet;
+    fn bitor(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+}
+impl ops::BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, other: CardSet) {
+        self.0 |= other.0;
+    }
+}
+impl ops::BitAnd for CardSet {
+    type Output = CardSet;
+    fn bitand(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+}
+impl ops::BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, other: CardSet) {
+        self.0 &= other.0;
+    }
+}
+impl ops::Sub for CardSet {
+    type Output = CardSet;
+    /// Cards in `self` that aren't in `other`.
+    fn sub(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+}
+impl ops::SubAssign for CardSet {
+    fn sub_assign(&mut self, other: CardSet) {
+        self.0 &= !other.0;
+    }
+}
+impl ops::Not for CardSet {
+    type Output = CardSet;
+    /// Every other card in the deck.
+    fn not(self) -> CardSet {
+        CardSet::ALL - self
+    }
+}
+
+impl iter::FromIterator<CardId> for CardSet {
+    fn from_iter<I: IntoIterator<Item = CardId>>(ids: I) -> Self {
+        let mut set = CardSet::EMPTY;
+        set.extend(ids);
+        set
+    }
+}
+impl iter::FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Self {
+        cards.into_iter().map(|card| card.id).collect()
+    }
+}
+impl Extend<CardId> for CardSet {
+    fn extend<I: IntoIterator<Item = CardId>>(&mut self, ids: I) {
+        for id in ids {
+            self.insert(id);
+        }
+    }
+}
+impl From<&[Card]> for CardSet {
+    fn from(cards: &[Card]) -> Self {
+        cards.iter().copied().collect()
+    }
+}
+impl From<&Deck> for CardSet {
+    fn from(deck: &Deck) -> Self {
+        deck.iter().copied().collect()
+    }
+}
+impl From<Deck> for CardSet {
+    fn from(deck: Deck) -> Self {
+        CardSet::from(&deck)
+    }
+}
+impl From<CardSet> for Deck {
+    fn from(set: CardSet) -> Self {
+        Deck(set.cards().collect())
+    }
+}
+impl IntoIterator for CardSet {
+    type Item = CardId;
+    type IntoIter = Iter;
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(self.cards().map(|card| card.code()))
+            .finish()
+    }
+}
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.cards().try_for_each(|card| write!(f, "{} ", card))
+    }
+}
+
+/// Iterator over the ids in a `CardSet`, from low to high.
+#[derive(Clone, Debug)]
+pub struct Iter(u64);
+impl Iterator for Iter {
+    type Item = CardId;
+
+    fn next(&mut self) -> Option<CardId> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        // Clear the lowest set bit.
+        self.0 &= self.0 - 1;
+        CardId::new(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+impl ExactSizeIterator for Iter {}
+
+#[test]
+pub fn test_card_set() {
+    let hand: Deck = "2H KH AS W Je".parse().unwrap();
+    let set = CardSet::from(&hand);
+    assert_eq!(set.len(), 5);
+    assert!(set.contains(crate::WIZARD.id));
+    assert!(!set.contains("3H".parse::<Card>().unwrap().id));
+    assert_eq!(Deck::from(set), "2H KH AS W Je".parse().unwrap());
+
+    assert_eq!(set.of_suit(Suit::Heart).len(), 2);
+    assert_eq!(set.of_suit(Suit::Club), CardSet::EMPTY);
+    assert_eq!((set & CardSet::WIZARDS).len(), 1);
+    assert_eq!((set - CardSet::JESTERS).len(), 4);
+    assert_eq!((!set).len(), 55);
+    assert_eq!(set | !set, CardSet::ALL);
+
+    assert_eq!(set.highest_of_suit(Suit::Heart), "KH".parse().ok());
+    assert_eq!(set.lowest_of_suit(Suit::Heart), "2H".parse().ok());
+    assert_eq!(set.highest_of_suit(Suit::Diamond), None);
+    assert_eq!(set.count_above("2H".parse().unwrap()), 1);
+    assert_eq!(set.count_above("KH".parse().unwrap()), 0);
+
+    let mut set = set;
+    assert!(set.remove(crate::WIZARD.id));
+    assert!(!set.remove(crate::WIZARD.id));
+    assert!(set.insert(crate::WIZARD.id));
+    assert_eq!(set.iter().len(), 5);
+
+    // The four Wizards are different cards.
+    let wizards: CardSet = CardId::all()
+        .filter(|id| id.card() == crate::WIZARD)
+        .collect();
+    assert_eq!(wizards, CardSet::WIZARDS);
+    assert_eq!(CardSet::from(&Deck::build()), CardSet::ALL);
+    for suit in Suit::ALL {
+        assert_eq!(
+            CardSet::suit(suit)
+                .cards()
+                .filter(|c| c.suit == suit)
+                .count(),
+            13
+        );
+    }
+}
@@ -1,52 +1,93 @@
-use crate::components::card::{Card, Rank, Suit};
+use crate::components::card::{Card, CardId, Rank, Suit};
+use crate::components::card_set::CardSet;
+use crate::error::WizardError;
+#[cfg(test)]
 use crate::{JESTER, WIZARD};
-use core::{fmt, ops};
+use core::{cmp, fmt, ops, str};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Deck(pub Vec<Card>);
 impl Deck {
+    /// All 60 cards in order of their `CardId`.
     pub fn build() -> Deck {
-        let mut deck: Vec<Card> = Vec::new();
-
-        // Build normal 52 card deck.
-        let ranks = [
-            Rank::Two,
-            Rank::Three,
-            Rank::Four,
-            Rank::Five,
-            Rank::Six,
-            Rank::Seven,
-            Rank::Eight,
-            Rank::Nine,
-            Rank::Ten,
-            Rank::Jack,
-            Rank::Queen,
-            Rank::King,
-            Rank::Ace,
-        ];
-        for suit in [Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade] {
-            for rank in ranks {
-                deck.push(Card { rank, suit });
-            }
-        }
-
-        // Add 4 Wizards and 4 Jesters.
-        for _ in 0..4 {
-            deck.push(WIZARD);
-            deck.push(JESTER);
-        }
+        Deck(CardId::all().map(CardId::card).collect())
+    }
 
-        Deck(deck)
+    /// Order a hand for showing to a player: Wizards first, then the `trump`
+    /// suit, the other suits, and Jesters last. Each suit goes high to low.
+    pub fn sort_for_display(&mut self, trump: Suit) {
+        self.sort_by_key(|card| {
+            let group = match (card.rank, card.suit) {
+                (Rank::Wizard, _) => 0,
+                (Rank::Jester, _) => 3,
+                (_, suit) if suit == trump => 1,
+                _ => 2,
+            };
+            (group, card.suit, cmp::Reverse(card.rank))
+        });
     }
 }
 impl fmt::Display for Deck {
     // Return space " " separated list of cards.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.iter().fold(Ok(()), |result, card| {
-            result.and_then(|_| write!(f, "{} ", card))
-        })
+        self.iter().try_for_each(|card| write!(f, "{} ", card))
+    }
+}
+impl str::FromStr for Deck {
+    type Err = WizardError;
+
+    /// Parses cards separated by spaces, e.g. `"10h QS W Je"`. A Wizard or
+    /// Jester that was already used becomes the next copy not used yet, any
+    /// other card showing up twice is an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut used = CardSet::EMPTY;
+        let mut cards = Vec::new();
+        for code in s.split_whitespace() {
+            let card: Card = code.parse()?;
+            // The copy asked for, then the ones after it.
+            let copy = card.id.copy() as usize - 1;
+            let first = card.id.index() - copy;
+            let copies = match card.rank {
+                Rank::Wizard | Rank::Jester => 4,
+                _ => 1,
+            };
+            let id = (0..copies)
+                .filter_map(|step| CardId::new((first + (copy + step) % copies) as u8))
+                .find(|id| !used.contains(*id));
+            let id = id.ok_or_else(|| WizardError::Parse(format!("{} more than once", code)))?;
+            used.insert(id);
+            cards.push(Card { id, ..card });
+        }
+        Ok(Deck(cards))
     }
 }
+
+#[test]
+pub fn test_parse_deck() {
+    let hand: Deck = "10h QS W Je".parse().unwrap();
+    assert_eq!(hand.len(), 4);
+    assert_eq!(hand[2], WIZARD);
+    assert_eq!(hand[3], JESTER);
+
+    // Display output reads back in, every Wizard and Jester its own copy.
+    let ids = |deck: &Deck| deck.iter().map(|card| card.id).collect::<Vec<_>>();
+    let deck = Deck::build();
+    assert_eq!(ids(&deck.to_string().parse().unwrap()), ids(&deck));
+    let hand: Deck = "W W Je2 Je Je 2H".parse().unwrap();
+    assert_eq!(CardSet::from(&hand).len(), 6);
+    assert_eq!(hand[3].id.copy(), 1);
+    assert_eq!(hand[4].id.copy(), 3);
+
+    assert!("2H 2H".parse::<Deck>().is_err());
+    assert!("W W W W W".parse::<Deck>().is_err());
+
+    assert_eq!("".parse(), Ok(Deck(Vec::new())));
+    assert!("10h 11h".parse::<Deck>().is_err());
+}
+
 impl ops::Deref for Deck {
     type Target = Vec<Card>;
     fn deref(&self) -> &Self::Target {
@@ -58,9 +99,30 @@ impl ops::DerefMut for Deck {
         self.0.as_mut()
     }
 }
+impl<'a> IntoIterator for &'a Deck {
+    type Item = &'a Card;
+    type IntoIter = std::slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
 
 #[test]
 pub fn test_build_deck() {
     let deck = Deck::build();
     assert_eq!(60, deck.len());
+    assert_eq!(deck.iter().filter(|card| **card == WIZARD).count(), 4);
+    assert_eq!(deck.iter().filter(|card| **card == JESTER).count(), 4);
+}
+
+#[test]
+pub fn test_sort_for_display() {
+    let mut hand: Deck = "Je 3C W KH 10S AC 5H W2".parse().unwrap();
+    hand.sort_for_display(Suit::Heart);
+    assert_eq!(hand, "W W2 KH 5H AC 3C 10S Je".parse().unwrap());
+
+    // With no trump the suits keep their usual order.
+    hand.sort_for_display(Suit::Suitless);
+    assert_eq!(hand, "W W2 AC 3C KH 5H 10S Je".parse().unwrap());
 }
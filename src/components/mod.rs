@@ -1,4 +1,5 @@
 pub mod card;
+pub mod card_set;
 pub mod deck;
 pub mod player;
 pub mod util;
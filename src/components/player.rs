@@ -1,39 +1,32 @@
-use crate::components::card::Card;
-use crate::components::deck::Deck;
-use crate::WIZARD;
+use crate::agents::{PlayerAgent, RandomAgent};
+use crate::components::card_set::CardSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Eq, PartialEq, Debug)]
-pub enum Operator {
-    Human,
-    Computer,
-}
-
-#[derive(Clone, Eq, PartialEq, Debug)]
+/// Agents aren't saved, resuming a game gives each seat the agent of its
+/// `Strategy` until the front end attaches its own again.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Player {
     pub name: String,
-    pub score: i16,
-    pub bet: u8,
-    pub tricks: u8,
-    pub card_played: Card,
-    pub hand: Deck,
-    pub operator: Operator,
-    pub original_position: usize,
+    pub hand: CardSet,
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_agent"))]
+    pub agent: Box<dyn PlayerAgent>,
+}
+
+fn default_agent() -> Box<dyn PlayerAgent> {
+    Box::new(RandomAgent)
 }
 impl Player {
     pub fn new() -> Player {
         Player {
             name: String::new(),
-            score: 0,
-            bet: 0,
-            tricks: 0,
-            card_played: WIZARD,
-            hand: Deck(Vec::new()),
-            operator: Operator::Computer,
-            original_position: 0,
+            hand: CardSet::EMPTY,
+            agent: default_agent(),
         }
     }
 
-    pub fn print_names(players: &Vec<Player>) {
+    pub fn print_names(players: &[Player]) {
         println!("\n Players");
         println!(" -------");
 
@@ -41,15 +34,9 @@ impl Player {
             println!(" {}", player.name);
         });
     }
-
-    pub fn print_score(players: &Vec<Player>) {
-        println!("\n Name    Score   Bet   Tricks");
-        println!(" ----------------------------");
-        players.iter().for_each(|player| {
-            println!(
-                " {:8} {:>2}     {:>2}     {:>2}",
-                player.name, player.score, player.bet, player.tricks
-            );
-        });
+}
+impl Default for Player {
+    fn default() -> Self {
+        Player::new()
     }
 }
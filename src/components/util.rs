@@ -1,42 +1,51 @@
+use crate::error::WizardError;
 use core::time;
 use rand::prelude::SliceRandom;
-use rand::thread_rng;
+use rand::Rng;
 use std::{io, thread};
 
 pub struct Util;
 impl Util {
-    pub fn shuffle_vec<T: std::clone::Clone>(mut items: Vec<T>) -> Vec<T> {
-        let slice = items.as_mut_slice();
-        let mut rng = thread_rng();
-        slice.shuffle(&mut rng);
+    pub fn shuffle_vec<T, R: Rng + ?Sized>(mut items: Vec<T>, rng: &mut R) -> Vec<T> {
+        items.shuffle(rng);
 
-        slice.to_vec()
+        items
     }
 
     pub fn print_wizard_ascii_art() {
         println!("           _                  _\n          (_)                | |\n __      ___ ______ _ _ __ __| |\n \\ \\ /\\ / / |_  / _` | \'__/ _` |\n  \\ V  V /| |/ / (_| | | | (_| |\n   \\_/\\_/ |_/___\\__,_|_|  \\__,_|\n");
     }
 
-    pub fn cli_next_string() -> String {
+    fn cli_read_line() -> Result<String, WizardError> {
         let mut buffer = String::new();
+        match io::stdin().read_line(&mut buffer) {
+            Ok(0) | Err(_) => Err(WizardError::InputClosed),
+            Ok(_) => Ok(String::from(buffer.trim())),
+        }
+    }
+
+    pub fn cli_next_string() -> Result<String, WizardError> {
         loop {
-            io::stdin().read_line(&mut buffer).unwrap();
-            match buffer.trim().parse::<String>() {
-                Ok(input) => {
-                    if !input.is_empty() {
-                        return input;
-                    }
-                }
-                Err(_) => {}
+            let input = Util::cli_read_line()?;
+            if !input.is_empty() {
+                return Ok(input);
             }
         }
     }
 
-    pub fn cli_next_num() -> u8 {
+    /// Next line of input, or `default` if the line is empty.
+    pub fn cli_next_string_or(default: &str) -> Result<String, WizardError> {
+        match Util::cli_read_line()?.as_str() {
+            "" => Ok(String::from(default)),
+            input => Ok(String::from(input)),
+        }
+    }
+
+    pub fn cli_next_num() -> Result<u8, WizardError> {
         loop {
-            match Util::cli_next_string().parse::<u8>() {
+            match Util::cli_next_string()?.parse::<u8>() {
                 Ok(num) => {
-                    return num;
+                    return Ok(num);
                 }
                 Err(_) => {
                     println!(" * Input must be a whole number * ");
@@ -45,24 +54,52 @@ impl Util {
         }
     }
 
-    pub fn cli_next_pos_num() -> u8 {
+    /// Pick one of `len` numbered options, returns the zero based index.
+    pub fn cli_next_index(len: usize) -> Result<usize, WizardError> {
         loop {
-            let num = Util::cli_next_num();
-            if num == 0 {
-                println!(" * Input must be a positive number * ");
+            let num = Util::cli_next_num()? as usize;
+            if num == 0 || num > len {
+                println!(" * Input must be a number from 1 to {} * ", len);
                 continue;
             }
-            return num;
+            return Ok(num - 1);
         }
     }
 
-    pub fn press_enter_to_(verb: &str) {
+    /// Like `cli_next_num`, but None if `?` was typed to ask for a hint.
+    pub fn cli_next_num_or_hint() -> Result<Option<u8>, WizardError> {
+        loop {
+            match Util::cli_next_string()?.as_str() {
+                "?" => return Ok(None),
+                input => match input.parse::<u8>() {
+                    Ok(num) => return Ok(Some(num)),
+                    Err(_) => println!(" * Input must be a whole number, or ? for a hint * "),
+                },
+            }
+        }
+    }
+
+    /// Like `cli_next_index`, but None if `?` was typed to ask for a hint.
+    pub fn cli_next_index_or_hint(len: usize) -> Result<Option<usize>, WizardError> {
+        loop {
+            let num = match Util::cli_next_num_or_hint()? {
+                Some(num) => num as usize,
+                None => return Ok(None),
+            };
+            if num == 0 || num > len {
+                println!(" * Input must be a number from 1 to {} * ", len);
+                continue;
+            }
+            return Ok(Some(num - 1));
+        }
+    }
+
+    pub fn press_enter_to_(verb: &str) -> Result<(), WizardError> {
         println!("\nPress Enter to {}...", verb);
-        let mut buffer = String::new();
-        io::stdin().read_line(&mut buffer).unwrap();
+        Util::cli_read_line().map(|_| ())
     }
 
-    pub fn sleep() {
-        thread::sleep(time::Duration::from_millis(500));
+    pub fn sleep(duration: time::Duration) {
+        thread::sleep(duration);
     }
 }
@@ -0,0 +1,355 @@
+use crate::agents::ismcts::Budget;
+use crate::agents::{HeuristicAgent, IsmctsAgent, PlayerAgent, RandomAgent};
+use crate::error::WizardError;
+use crate::scoring::{ClassicScoring, OfficialScoring, ScoringRule, ZeroBidBonus};
+use crate::{num_rounds, MAX_PLAYERS, MIN_PLAYERS};
+use core::{fmt, str, time};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum PlayerKind {
+    Human,
+    Computer,
+}
+
+/// How a computer seat makes its decisions.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Strategy {
+    /// Bets at random and plays the first card that follows suit.
+    Random,
+    /// Bets the tricks its hand is expected to win and plays to make them.
+    #[default]
+    Heuristic,
+    /// Searches every decision with `IsmctsAgent`, the strongest and slowest.
+    Ismcts(Budget),
+}
+impl Strategy {
+    pub fn agent(self) -> Box<dyn PlayerAgent> {
+        match self {
+            Strategy::Random => Box::new(RandomAgent),
+            Strategy::Heuristic => Box::new(HeuristicAgent),
+            Strategy::Ismcts(budget) => Box::new(IsmctsAgent::new(budget)),
+        }
+    }
+}
+
+/// Named strength of a computer seat, for picking one at the table.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Difficulty {
+    /// Random bets, plays the first card that follows suit.
+    Novice,
+    #[default]
+    Intermediate,
+    /// Searches every move, slower to decide.
+    Expert,
+}
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [
+        Difficulty::Novice,
+        Difficulty::Intermediate,
+        Difficulty::Expert,
+    ];
+
+    pub fn strategy(self) -> Strategy {
+        match self {
+            Difficulty::Novice => Strategy::Random,
+            Difficulty::Intermediate => Strategy::Heuristic,
+            Difficulty::Expert => Strategy::Ismcts(Budget::default()),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Novice => "novice",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Expert => "expert",
+        }
+    }
+}
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl str::FromStr for Difficulty {
+    type Err = WizardError;
+
+    /// Parses the name of a level or its first letter, e.g. `expert` or `e`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        Difficulty::ALL
+            .iter()
+            .copied()
+            .find(|level| level.name() == s || level.name()[..1] == s)
+            .ok_or_else(|| WizardError::Parse(format!("difficulty {}", s)))
+    }
+}
+
+#[test]
+pub fn test_parse_difficulty() {
+    assert_eq!("novice".parse(), Ok(Difficulty::Novice));
+    assert_eq!("Expert".parse(), Ok(Difficulty::Expert));
+    assert_eq!("i".parse(), Ok(Difficulty::Intermediate));
+    assert!("".parse::<Difficulty>().is_err());
+    assert!("hard".parse::<Difficulty>().is_err());
+    assert_eq!(Difficulty::Novice.strategy(), Strategy::Random);
+    assert_eq!(Difficulty::default().strategy(), Strategy::default());
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PlayerConfig {
+    pub name: String,
+    pub kind: PlayerKind,
+    /// Agent the engine gives the seat. Front ends replace it for humans.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub strategy: Strategy,
+}
+
+/// Built in scoring rules that can be picked by name.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Scoring {
+    Official,
+    Classic,
+    /// Official scoring plus a bonus for making a bet of zero.
+    ZeroBidBonus(i16),
+}
+impl Scoring {
+    pub fn rule(self) -> Box<dyn ScoringRule> {
+        match self {
+            Scoring::Official => Box::new(OfficialScoring),
+            Scoring::Classic => Box::new(ClassicScoring),
+            Scoring::ZeroBidBonus(bonus) => Box::new(ZeroBidBonus {
+                rule: OfficialScoring,
+                bonus,
+            }),
+        }
+    }
+}
+/// Scores the same as `rule`, without boxing it.
+impl ScoringRule for Scoring {
+    fn score(&self, bet: u8, tricks: u8) -> i16 {
+        match *self {
+            Scoring::Official => OfficialScoring.score(bet, tricks),
+            Scoring::Classic => ClassicScoring.score(bet, tricks),
+            Scoring::ZeroBidBonus(bonus) => ZeroBidBonus {
+                rule: OfficialScoring,
+                bonus,
+            }
+            .score(bet, tricks),
+        }
+    }
+}
+
+impl str::FromStr for Scoring {
+    type Err = WizardError;
+
+    /// Parses `official`, `classic`, `zero-bonus` or `zero-bonus=<points>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "official" => Ok(Scoring::Official),
+            "classic" => Ok(Scoring::Classic),
+            "zero-bonus" => Ok(Scoring::ZeroBidBonus(10)),
+            other => match other.strip_prefix("zero-bonus=").map(|bonus| bonus.parse()) {
+                Some(Ok(bonus)) => Ok(Scoring::ZeroBidBonus(bonus)),
+                _ => Err(WizardError::Parse(format!("scoring rule {}", s))),
+            },
+        }
+    }
+}
+
+#[test]
+pub fn test_parse_scoring() {
+    assert_eq!("official".parse(), Ok(Scoring::Official));
+    assert_eq!("Classic".parse(), Ok(Scoring::Classic));
+    assert_eq!("zero-bonus".parse(), Ok(Scoring::ZeroBidBonus(10)));
+    assert_eq!("zero-bonus=5".parse(), Ok(Scoring::ZeroBidBonus(5)));
+    assert!("zero-bonus=lots".parse::<Scoring>().is_err());
+    assert!("house".parse::<Scoring>().is_err());
+}
+
+/// Optional changes to the standard rules.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct RuleVariants {
+    /// The last player to bet may not make the bets add up to the number of
+    /// cards dealt, so at least one player must miss their bet.
+    pub uneven_bets: bool,
+}
+
+/// Everything needed to start a game. Use `GameConfig::builder()`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct GameConfig {
+    /// Seats in the order they sit at the table.
+    pub players: Vec<PlayerConfig>,
+    pub num_rounds: usize,
+    pub scoring: Scoring,
+    pub variants: RuleVariants,
+    /// None picks a random seed when the game starts.
+    pub seed: Option<u64>,
+    /// How long front ends pause between computer moves.
+    pub delay: time::Duration,
+}
+impl GameConfig {
+    pub fn builder() -> GameConfigBuilder {
+        GameConfigBuilder::default()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GameConfigBuilder {
+    players: Vec<PlayerConfig>,
+    num_rounds: Option<usize>,
+    scoring: Scoring,
+    variants: RuleVariants,
+    seed: Option<u64>,
+    delay: time::Duration,
+}
+impl Default for GameConfigBuilder {
+    fn default() -> Self {
+        GameConfigBuilder {
+            players: Vec::new(),
+            num_rounds: None,
+            scoring: Scoring::Official,
+            variants: RuleVariants::default(),
+            seed: None,
+            delay: time::Duration::from_millis(500),
+        }
+    }
+}
+impl GameConfigBuilder {
+    pub fn player(mut self, name: &str, kind: PlayerKind) -> Self {
+        self.players.push(PlayerConfig {
+            name: String::from(name),
+            kind,
+            strategy: Strategy::default(),
+        });
+        self
+    }
+
+    /// Replace all seats with `players`.
+    pub fn players(mut self, players: Vec<PlayerConfig>) -> Self {
+        self.players = players;
+        self
+    }
+
+    pub fn human(self, name: &str) -> Self {
+        self.player(name, PlayerKind::Human)
+    }
+
+    pub fn computer(self, name: &str) -> Self {
+        self.player(name, PlayerKind::Computer)
+    }
+
+    /// A computer seat using `strategy` instead of the default.
+    pub fn computer_with(mut self, name: &str, strategy: Strategy) -> Self {
+        self = self.computer(name);
+        if let Some(player) = self.players.last_mut() {
+            player.strategy = strategy;
+        }
+        self
+    }
+
+    /// Play fewer rounds than the default of the whole deck in the last round.
+    pub fn num_rounds(mut self, num_rounds: usize) -> Self {
+        self.num_rounds = Some(num_rounds);
+        self
+    }
+
+    pub fn scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    pub fn variants(mut self, variants: RuleVariants) -> Self {
+        self.variants = variants;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn delay(mut self, delay: time::Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn build(self) -> Result<GameConfig, WizardError> {
+        let num_players = self.players.len();
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&num_players) {
+            return Err(WizardError::PlayerCount(num_players));
+        }
+
+        let max_rounds = num_rounds(num_players);
+        let num_rounds = self.num_rounds.unwrap_or(max_rounds);
+        if num_rounds == 0 || num_rounds > max_rounds {
+            return Err(WizardError::RoundCount {
+                num_rounds,
+                max_rounds,
+            });
+        }
+
+        Ok(GameConfig {
+            players: self.players,
+            num_rounds,
+            scoring: self.scoring,
+            variants: self.variants,
+            seed: self.seed,
+            delay: self.delay,
+        })
+    }
+}
+
+#[test]
+pub fn test_build_config() {
+    let config = GameConfig::builder()
+        .human("Nils")
+        .computer("Merlin")
+        .computer("Oz")
+        .build()
+        .unwrap();
+    assert_eq!(config.players.len(), 3);
+    assert_eq!(config.players[0].kind, PlayerKind::Human);
+    assert_eq!(config.players[1].strategy, Strategy::Heuristic);
+    assert_eq!(config.num_rounds, 20);
+    assert_eq!(config.scoring, Scoring::Official);
+    assert_eq!(config.seed, None);
+
+    let config = GameConfig::builder()
+        .computer("Merlin")
+        .computer("Oz")
+        .computer_with("Kvothe", Strategy::Random)
+        .num_rounds(5)
+        .scoring(Scoring::Classic)
+        .seed(7)
+        .build()
+        .unwrap();
+    assert_eq!(config.num_rounds, 5);
+    assert_eq!(config.players[2].strategy, Strategy::Random);
+    assert_eq!(config.scoring, Scoring::Classic);
+    assert_eq!(config.seed, Some(7));
+
+    // Too few players.
+    assert_eq!(
+        GameConfig::builder().human("Nils").build(),
+        Err(WizardError::PlayerCount(1))
+    );
+
+    // More rounds than there are cards for.
+    let too_long = GameConfig::builder()
+        .computer("Merlin")
+        .computer("Oz")
+        .computer("Kvothe")
+        .num_rounds(21)
+        .build();
+    assert!(too_long.is_err());
+}
@@ -0,0 +1,80 @@
+use crate::components::card::{Card, Suit};
+use crate::game::Phase;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Everything that can go wrong when setting up or playing a game.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum WizardError {
+    /// Wizard is played by 3 to 6 players.
+    PlayerCount(usize),
+    /// More rounds than there are cards for, or none at all.
+    RoundCount {
+        num_rounds: usize,
+        max_rounds: usize,
+    },
+    /// The deck ran out of cards while dealing.
+    DeckExhausted,
+    /// The same physical card, by `CardId`, is in the deck twice.
+    DuplicateCard(Card),
+    /// The action can't be taken while the game is in this phase.
+    WrongPhase(Phase),
+    /// Trump must be one of the four suits.
+    InvalidTrumpSuit(Suit),
+    BetTooHigh {
+        bet: u8,
+        max_bet: u8,
+    },
+    /// The last bet would make the bets add up to the number of tricks.
+    EvenBets(u8),
+    CardNotInHand,
+    /// Holding a card of the lead suit but tried to play something else.
+    MustFollowSuit(Suit),
+    /// Standard input was closed or could not be read.
+    InputClosed,
+    /// Text that could not be understood.
+    Parse(String),
+    /// A saved game could not be written or read back.
+    SaveFile(String),
+}
+impl fmt::Display for WizardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WizardError::PlayerCount(num_players) => write!(
+                f,
+                "Wizard is played by {} to {} players, not {}",
+                crate::MIN_PLAYERS,
+                crate::MAX_PLAYERS,
+                num_players
+            ),
+            WizardError::RoundCount {
+                num_rounds,
+                max_rounds,
+            } => write!(
+                f,
+                "This table can play 1 to {} rounds, not {}",
+                max_rounds, num_rounds
+            ),
+            WizardError::DeckExhausted => write!(f, "Ran out of cards while dealing"),
+            WizardError::DuplicateCard(card) => write!(f, "The deck holds {} twice", card),
+            WizardError::WrongPhase(phase) => write!(f, "Can't do that while {:?}", phase),
+            WizardError::InvalidTrumpSuit(_) => write!(f, "Hey! Gotta pick what's offered here!"),
+            WizardError::BetTooHigh { max_bet, .. } => write!(
+                f,
+                "Yer a cocky one eh?\nBet must be in the range of 0 to {}.",
+                max_bet
+            ),
+            WizardError::EvenBets(_) => {
+                write!(f, "Hey! Bets can't add up to the number of tricks!")
+            }
+            WizardError::CardNotInHand => write!(f, "Hey! Gotta pick what's offered!"),
+            WizardError::MustFollowSuit(_) => write!(f, "Hey! Gotta follow suit!"),
+            WizardError::InputClosed => write!(f, "Input was closed"),
+            WizardError::Parse(text) => write!(f, "Can't understand {}", text),
+            WizardError::SaveFile(reason) => write!(f, "Problem with the save file: {}", reason),
+        }
+    }
+}
+impl std::error::Error for WizardError {}
@@ -0,0 +1,63 @@
+use crate::components::card::{Card, Suit};
+use crate::components::card_set::CardSet;
+use crate::table::PlayerId;
+use crate::Play;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+
+/// Everything that happens in a game, in the order it happens. Seats are
+/// indices into `GameState::players`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Event {
+    RoundStarted {
+        round_num: usize,
+        dealer: PlayerId,
+        leader: PlayerId,
+    },
+    /// Sent once per seat. Observers showing a single player should only look
+    /// at their own seat.
+    CardsDealt {
+        seat: PlayerId,
+        hand: CardSet,
+    },
+    /// The card flipped after dealing, None in the last round.
+    TrumpRevealed(Option<Card>),
+    /// The dealer picked a suit after a Wizard was flipped.
+    TrumpSuitChosen {
+        seat: PlayerId,
+        suit: Suit,
+    },
+    BetPlaced {
+        seat: PlayerId,
+        bet: u8,
+    },
+    CardPlayed(Play),
+    /// The winning play of a completed trick.
+    TrickWon(Play),
+    /// Points earned this round and total scores, by seat.
+    RoundScored {
+        round_num: usize,
+        points: Vec<i16>,
+        scores: Vec<i16>,
+    },
+    /// Final scores by seat.
+    GameOver {
+        scores: Vec<i16>,
+    },
+}
+
+/// Subscriber to the events of a game, see `GameState::add_observer`.
+/// Logging, UIs, statistics and replays hook in here instead of the rules.
+pub trait GameObserver: fmt::Debug {
+    fn notify(&mut self, event: &Event);
+}
+
+/// Forward events to another thread. Events are dropped once the receiver hangs up.
+impl GameObserver for mpsc::Sender<Event> {
+    fn notify(&mut self, event: &Event) {
+        let _ = self.send(event.clone());
+    }
+}
@@ -0,0 +1,1358 @@
+use crate::agents::{PlayerAgent, RandomAgent};
+use crate::components::card::{Card, CardId, Suit};
+use crate::components::card_set::CardSet;
+use crate::components::deck::Deck;
+use crate::components::player::Player;
+use crate::components::util::Util;
+use crate::config::{GameConfig, Scoring};
+use crate::error::WizardError;
+use crate::events::{Event, GameObserver};
+use crate::history::{Round, Trick};
+#[cfg(feature = "serde")]
+use crate::save::SavedGame;
+use crate::scoring::ScoringRule;
+use crate::table::{PlayerId, Table};
+use crate::{
+    calc_points, calc_winner, calc_winner_of_trick, lead_suit, legal_card_set, num_rounds, Play,
+    MAX_PLAYERS, MIN_PLAYERS, WIZARD,
+};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, mem};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Phase {
+    /// Waiting for the next round to be dealt.
+    Dealing,
+    /// A Wizard was flipped and the dealer must pick the trump suit.
+    ChoosingTrump,
+    Betting,
+    Playing,
+    GameOver,
+}
+
+/// What a call to `GameState::step` did.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Action {
+    ChoseTrump(Suit),
+    Bet(u8),
+    /// Card played, and the winning play if it completed the trick.
+    Played(Card, Option<Play>),
+}
+
+/// Read-only snapshot of the game from the point of view of one seat.
+/// Only holds what that player is allowed to know, borrowed from the game.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GameView<'a> {
+    pub seat: PlayerId,
+    pub hand: CardSet,
+    pub trump: Option<Card>,
+    pub lead_suit: Suit,
+    /// Cards played so far in the current trick, in order.
+    pub trick: &'a [Play],
+    /// Every card played this round, the current trick included.
+    pub played: CardSet,
+    /// Tricks already finished this round, in order.
+    pub past_tricks: &'a [Trick],
+    pub round_num: usize,
+    pub num_rounds: usize,
+    pub dealer: PlayerId,
+    pub leader: PlayerId,
+    /// Bets by seat, None if the player has not bet yet this round.
+    pub bets: &'a [Option<u8>],
+    /// Bets this player is allowed to place right now, empty unless it's
+    /// their turn to bet.
+    pub legal_bets: &'a [u8],
+    /// Tricks won this round by seat.
+    pub tricks: &'a [u8],
+    pub scores: &'a [i16],
+    /// Rule from the config the round is scored by. A rule passed to
+    /// `GameState::with_scoring` isn't shown here.
+    pub scoring: Scoring,
+}
+
+impl GameView<'_> {
+    /// Cards in hand that may be played into the current trick.
+    pub fn legal_cards(&self) -> Vec<Card> {
+        legal_card_set(self.hand, self.lead_suit).cards().collect()
+    }
+
+    /// Suit of the trump card, Suitless if there is none or a flipped Wizard
+    /// has no suit chosen yet.
+    pub fn trump_suit(&self) -> Suit {
+        self.trump.map_or(Suit::Suitless, |card| card.suit)
+    }
+}
+
+/// Headless game engine. Owns the deck, seats, dealer, trump, bets and tricks,
+/// and only advances through method calls. No printing or reading of stdin.
+/// All shuffling and computer decisions draw from `rng`, so a game started
+/// from the same seed plays out the same way.
+#[derive(Debug)]
+pub struct GameState<R = ChaCha12Rng> {
+    config: GameConfig,
+    /// Shared with agents while they look at a `GameView` borrowing the game.
+    rng: RefCell<R>,
+    seed: Option<u64>,
+    scoring: Box<dyn ScoringRule>,
+    players: Vec<Player>,
+    /// Bets by seat, None until the player has bet this round.
+    bets: Vec<Option<u8>>,
+    /// Bets the player whose turn it is may place, empty outside of betting.
+    legal_bets: Vec<u8>,
+    /// Tricks won this round by seat.
+    tricks: Vec<u8>,
+    scores: Vec<i16>,
+    deck: Deck,
+    round_num: usize,
+    table: Table,
+    leader: PlayerId,
+    turn: PlayerId,
+    trump: Option<Card>,
+    trick: Vec<Play>,
+    history: Vec<Round>,
+    phase: Phase,
+    observers: Vec<Box<dyn GameObserver>>,
+}
+impl GameState {
+    /// New game seeded from `config.seed`, or a random seed if there is none.
+    /// Every seat is played by the agent of its `Strategy` until `set_agent`
+    /// is called.
+    pub fn new(config: GameConfig) -> Result<GameState, WizardError> {
+        let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut game = GameState::with_rng(config, ChaCha12Rng::seed_from_u64(seed))?;
+        game.seed = Some(seed);
+        Ok(game)
+    }
+
+    /// Snapshot of the game to write to a file and `resume` later. Agents,
+    /// observers and a custom scoring rule aren't part of it. The rng state
+    /// is saved as well, so the resumed game plays on the way this one does.
+    #[cfg(feature = "serde")]
+    pub fn save(&self) -> SavedGame {
+        let players = self
+            .players
+            .iter()
+            .map(|player| Player {
+                name: player.name.clone(),
+                hand: player.hand,
+                ..Player::new()
+            })
+            .collect();
+        SavedGame {
+            config: self.config.clone(),
+            seed: self.seed,
+            rng: self.rng.borrow().clone(),
+            players,
+            bets: self.bets.clone(),
+            tricks: self.tricks.clone(),
+            scores: self.scores.clone(),
+            deck: self.deck.clone(),
+            round_num: self.round_num,
+            leader: self.leader,
+            turn: self.turn,
+            trump: self.trump,
+            trick: self.trick.clone(),
+            history: self.history.clone(),
+            phase: self.phase,
+        }
+    }
+
+    /// Pick a saved game back up. Every seat is played by the agent of its
+    /// `Strategy` until `set_agent` is called.
+    #[cfg(feature = "serde")]
+    pub fn resume(saved: SavedGame) -> Result<GameState, WizardError> {
+        let mut game = GameState::with_rng(saved.config, saved.rng)?;
+        if saved.players.len() != game.players.len() {
+            return Err(WizardError::SaveFile(String::from(
+                "seats don't match the players in the config",
+            )));
+        }
+
+        game.seed = saved.seed;
+        let agents = game.players.drain(..).map(|player| player.agent);
+        game.players = saved
+            .players
+            .into_iter()
+            .zip(agents)
+            .map(|(player, agent)| Player { agent, ..player })
+            .collect();
+        game.bets = saved.bets;
+        game.tricks = saved.tricks;
+        game.scores = saved.scores;
+        game.deck = saved.deck;
+        game.round_num = saved.round_num;
+        game.table.set_round(saved.round_num);
+        game.leader = saved.leader;
+        game.turn = saved.turn;
+        game.trump = saved.trump;
+        game.trick = saved.trick;
+        game.history = saved.history;
+        game.phase = saved.phase;
+        game.check_resumed()?;
+        game.update_legal_bets();
+        Ok(game)
+    }
+
+    /// Makes sure a resumed game is one play could have reached, so a
+    /// damaged save file is turned down instead of crashing the game later.
+    #[cfg(feature = "serde")]
+    fn check_resumed(&self) -> Result<(), WizardError> {
+        let broken = |reason: &str| Err(WizardError::SaveFile(String::from(reason)));
+        let num_players = self.players.len();
+        if self.turn.index() >= num_players || self.leader.index() >= num_players {
+            return broken("turn or leader isn't one of the seats");
+        }
+        if self.round_num > self.config.num_rounds {
+            return broken("round is past the last one");
+        }
+        let seat_counts = [self.bets.len(), self.tricks.len(), self.scores.len()];
+        if seat_counts.iter().any(|count| *count != num_players) {
+            return broken("bets, tricks or scores don't match the seats");
+        }
+        if self.trick.len() >= num_players
+            || self
+                .trick
+                .iter()
+                .any(|play| play.seat.index() >= num_players)
+        {
+            return broken("current trick doesn't fit the table");
+        }
+
+        let tricks_done = match self.phase {
+            Phase::Playing => self.history.last().map_or(0, |round| round.tricks.len()),
+            _ => 0,
+        };
+        for seat in self.table.seats() {
+            let played = self.trick.iter().any(|play| play.seat == seat);
+            let hand_size = match self.phase {
+                Phase::ChoosingTrump | Phase::Betting => Some(self.round_num),
+                Phase::Playing => self
+                    .round_num
+                    .checked_sub(tricks_done + played as usize)
+                    .filter(|cards| *cards > 0 || played),
+                Phase::Dealing | Phase::GameOver => Some(0),
+            };
+            if hand_size != Some(self.player(seat).hand.len()) {
+                return broken("hands don't match the round and trick");
+            }
+        }
+
+        // Whose turn it is follows from the dealer, the bets and the trick.
+        let round = self.history.last();
+        let turn_fits = match self.phase {
+            Phase::ChoosingTrump => {
+                self.turn == self.dealer() && self.bets.iter().all(Option::is_none)
+            }
+            Phase::Betting => {
+                // Seats from left of the dealer up to `turn` have bet.
+                let mut has_bet = true;
+                round.is_some_and(|round| round.bets.is_empty())
+                    && self.table.betting_order().all(|id| {
+                        has_bet &= id != self.turn;
+                        self.bets[id.index()].is_some() == has_bet
+                    })
+            }
+            Phase::Playing => {
+                let bets: Option<Vec<u8>> = self.bets.iter().copied().collect();
+                let won = |seat| {
+                    let tricks = round.map_or(&[][..], |round| &round.tricks);
+                    tricks
+                        .iter()
+                        .filter(|trick| trick.winner.seat == seat)
+                        .count()
+                };
+                let mut order = self.table.play_order(self.leader);
+                round.is_some_and(|round| bets.as_ref() == Some(&round.bets))
+                    && self
+                        .table
+                        .seats()
+                        .all(|seat| won(seat) == self.tricks[seat.index()] as usize)
+                    && self
+                        .trick
+                        .iter()
+                        .all(|play| order.next() == Some(play.seat))
+                    && order.next() == Some(self.turn)
+            }
+            Phase::Dealing | Phase::GameOver => true,
+        };
+        let leader_fits = match self.phase {
+            Phase::ChoosingTrump | Phase::Betting => {
+                self.leader == self.table.left_of(self.dealer())
+            }
+            _ => true,
+        };
+        if !turn_fits || !leader_fits {
+            return broken("turn, bets or tricks don't follow from the round");
+        }
+
+        // Each physical card is in one place only: a hand, the trick, the
+        // deck, the trump or a trick already played this round.
+        let mut seen = CardSet::EMPTY;
+        let hands = self.players.iter().flat_map(|player| player.hand.iter());
+        let trick = self.trick.iter().map(|play| play.card.id);
+        let deck = self.deck.iter().chain(&self.trump).map(|card| card.id);
+        let played = round.into_iter().flat_map(Round::plays);
+        let played = played.map(|play| play.card.id);
+        let mut ids = hands.chain(trick).chain(deck).chain(played);
+        if ids.any(|id| !seen.insert(id)) {
+            return broken("the same card is in two places");
+        }
+        Ok(())
+    }
+}
+impl<R: RngCore> GameState<R> {
+    /// New game drawing from `rng` instead of a seed.
+    pub fn with_rng(config: GameConfig, rng: R) -> Result<GameState<R>, WizardError> {
+        let num_players = config.players.len();
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&num_players) {
+            return Err(WizardError::PlayerCount(num_players));
+        }
+        let max_rounds = num_rounds(num_players);
+        if config.num_rounds == 0 || config.num_rounds > max_rounds {
+            return Err(WizardError::RoundCount {
+                num_rounds: config.num_rounds,
+                max_rounds,
+            });
+        }
+
+        let players = config
+            .players
+            .iter()
+            .map(|player| Player {
+                name: player.name.clone(),
+                agent: player.strategy.agent(),
+                ..Player::new()
+            })
+            .collect();
+        Ok(GameState {
+            scoring: config.scoring.rule(),
+            config,
+            rng: RefCell::new(rng),
+            seed: None,
+            players,
+            bets: vec![None; num_players],
+            legal_bets: Vec::new(),
+            tricks: vec![0; num_players],
+            scores: vec![0; num_players],
+            deck: Deck(Vec::new()),
+            round_num: 0,
+            table: Table::new(num_players),
+            leader: PlayerId(0),
+            turn: PlayerId(0),
+            trump: None,
+            trick: Vec::new(),
+            history: Vec::new(),
+            phase: Phase::Dealing,
+            observers: Vec::new(),
+        })
+    }
+
+    /// Score rounds with a custom rule instead of `config.scoring`.
+    pub fn with_scoring(mut self, scoring: Box<dyn ScoringRule>) -> GameState<R> {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Let `agent` make the decisions for the player at `seat`.
+    pub fn set_agent(&mut self, seat: PlayerId, agent: Box<dyn PlayerAgent>) {
+        self.players[seat.index()].agent = agent;
+    }
+
+    /// Send every event from now on to `observer`.
+    pub fn add_observer(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Seed the game was started from. None if it was given its own `Rng`.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Players in seat order, see `PlayerId`.
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn player(&self, id: PlayerId) -> &Player {
+        &self.players[id.index()]
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn round_num(&self) -> usize {
+        self.round_num
+    }
+
+    pub fn num_rounds(&self) -> usize {
+        self.config.num_rounds
+    }
+
+    /// The player who dealt the current round.
+    pub fn dealer(&self) -> PlayerId {
+        self.table.dealer()
+    }
+
+    /// The player who leads the current trick.
+    pub fn leader(&self) -> PlayerId {
+        self.leader
+    }
+
+    /// The player who has to act next.
+    pub fn turn(&self) -> PlayerId {
+        self.turn
+    }
+
+    /// The flipped trump card. If a Wizard was flipped its suit is the one
+    /// chosen by the dealer. None if no card was left to flip.
+    pub fn trump(&self) -> Option<Card> {
+        self.trump
+    }
+
+    pub fn trump_suit(&self) -> Suit {
+        match self.trump {
+            Some(card) => card.suit,
+            None => Suit::Suitless,
+        }
+    }
+
+    /// Cards played so far in the current trick, in order.
+    pub fn trick(&self) -> &[Play] {
+        &self.trick
+    }
+
+    /// Every card played this round, the current trick included.
+    pub fn played(&self) -> CardSet {
+        let mut played: CardSet = self.trick.iter().map(|play| play.card).collect();
+        if let Some(round) = self.history.last() {
+            played.extend(round.plays().map(|play| play.card.id));
+        }
+        played
+    }
+
+    /// Every round dealt so far, the last one may still be in progress.
+    pub fn history(&self) -> &[Round] {
+        &self.history
+    }
+
+    /// Suit of the first non-Jester card in the current trick.
+    /// Suitless if nothing has been led yet or a Wizard was led.
+    pub fn lead_suit(&self) -> Suit {
+        lead_suit(self.trick.iter().map(|play| &play.card))
+    }
+
+    /// Bets by seat, None if the player has not bet yet this round.
+    pub fn bets(&self) -> &[Option<u8>] {
+        &self.bets
+    }
+
+    /// Bets the player whose turn it is may place, empty outside of betting.
+    pub fn legal_bets(&self) -> &[u8] {
+        &self.legal_bets
+    }
+
+    /// Tricks won this round by seat.
+    pub fn tricks(&self) -> &[u8] {
+        &self.tricks
+    }
+
+    /// Total scores by seat.
+    pub fn scores(&self) -> &[i16] {
+        &self.scores
+    }
+
+    /// What the player at `seat` can see of the game.
+    pub fn view(&self, seat: PlayerId) -> GameView<'_> {
+        GameView {
+            seat,
+            hand: self.player(seat).hand,
+            trump: self.trump,
+            lead_suit: self.lead_suit(),
+            trick: &self.trick,
+            played: self.played(),
+            past_tricks: self
+                .history
+                .last()
+                .map_or(&[], |round| round.tricks.as_slice()),
+            round_num: self.round_num,
+            num_rounds: self.config.num_rounds,
+            dealer: self.dealer(),
+            leader: self.leader,
+            bets: &self.bets,
+            legal_bets: match seat == self.turn {
+                true => &self.legal_bets,
+                false => &[],
+            },
+            tricks: &self.tricks,
+            scores: &self.scores,
+            scoring: self.config.scoring,
+        }
+    }
+
+    /// Ask the agent of the player whose turn it is to act, and apply its choice.
+    pub fn step(&mut self) -> Result<Action, WizardError> {
+        if let Phase::Dealing | Phase::GameOver = self.phase {
+            return Err(WizardError::WrongPhase(self.phase));
+        }
+        if self.phase == Phase::Playing && self.player(self.turn).hand.is_empty() {
+            return Err(WizardError::CardNotInHand);
+        }
+
+        // The agent is set aside while it decides, the view borrows the rest.
+        let seat = self.turn.index();
+        let mut agent = mem::replace(&mut self.players[seat].agent, Box::new(RandomAgent));
+        let action = self.ask(agent.as_mut());
+        self.players[seat].agent = agent;
+
+        match action {
+            Action::ChoseTrump(suit) => self.choose_trump_suit(suit).map(|_| action),
+            Action::Bet(bet) => self.place_bet(bet).map(|_| action),
+            Action::Played(card, _) => Ok(Action::Played(card, self.play(card)?)),
+        }
+    }
+
+    /// What `agent` picks for the player whose turn it is, not applied yet.
+    fn ask(&self, agent: &mut dyn PlayerAgent) -> Action {
+        let view = self.view(self.turn);
+        let rng = &mut *self.rng.borrow_mut();
+        match self.phase {
+            Phase::ChoosingTrump => Action::ChoseTrump(agent.choose_trump_suit(&view, rng)),
+            Phase::Betting => Action::Bet(agent.choose_bet(&view, rng)),
+            _ => Action::Played(agent.choose_card(&view, rng), None),
+        }
+    }
+
+    /// Cards the player whose turn it is may play into the current trick.
+    pub fn legal_cards(&self) -> Vec<Card> {
+        legal_card_set(self.player(self.turn).hand, self.lead_suit())
+            .cards()
+            .collect()
+    }
+
+    /// Shuffle a fresh deck and deal the next round.
+    pub fn deal(&mut self) -> Result<Option<Card>, WizardError> {
+        let deck = Deck(Util::shuffle_vec(Deck::build().0, self.rng.get_mut()));
+        self.deal_from(deck)
+    }
+
+    /// Deal the next round from the end of `deck` and flip the next card for trump.
+    /// Every card in `deck` needs its own `CardId`, copies of Wizards and
+    /// Jesters included.
+    pub fn deal_from(&mut self, mut deck: Deck) -> Result<Option<Card>, WizardError> {
+        if self.phase != Phase::Dealing {
+            return Err(WizardError::WrongPhase(self.phase));
+        }
+        if deck.len() < (self.round_num + 1) * self.players.len() {
+            return Err(WizardError::DeckExhausted);
+        }
+        let mut seen = CardSet::EMPTY;
+        if let Some(card) = deck.iter().find(|card| !seen.insert(card.id)) {
+            return Err(WizardError::DuplicateCard(*card));
+        }
+
+        self.round_num += 1;
+        self.table.set_round(self.round_num);
+        self.leader = self.table.left_of(self.dealer());
+        self.turn = self.leader;
+        self.trick.clear();
+
+        // Deal starting with the player left of the dealer and reset stats.
+        for player in self.players.iter_mut() {
+            player.hand = CardSet::EMPTY;
+        }
+        self.bets.fill(None);
+        self.tricks.fill(0);
+        for _ in 0..self.round_num {
+            for id in self.table.betting_order() {
+                if let Some(card) = deck.pop() {
+                    self.players[id.index()].hand.insert(card.id);
+                }
+            }
+        }
+
+        self.trump = deck.pop();
+        self.deck = deck;
+        self.history.push(Round {
+            round_num: self.round_num,
+            dealer: self.dealer(),
+            trump: self.trump,
+            trump_suit: self.trump_suit(),
+            bets: Vec::new(),
+            tricks: Vec::new(),
+            tricks_won: Vec::new(),
+            points: Vec::new(),
+        });
+
+        self.emit(Event::RoundStarted {
+            round_num: self.round_num,
+            dealer: self.dealer(),
+            leader: self.leader,
+        });
+        for seat in self.table.seats() {
+            let hand = self.player(seat).hand;
+            self.emit(Event::CardsDealt { seat, hand });
+        }
+        self.emit(Event::TrumpRevealed(self.trump));
+
+        self.phase = match self.trump {
+            Some(card) if card == WIZARD => {
+                self.turn = self.dealer();
+                Phase::ChoosingTrump
+            }
+            _ => Phase::Betting,
+        };
+        self.update_legal_bets();
+        Ok(self.trump)
+    }
+
+    /// Dealer picks the trump suit after a Wizard was flipped.
+    pub fn choose_trump_suit(&mut self, suit: Suit) -> Result<(), WizardError> {
+        if self.phase != Phase::ChoosingTrump {
+            return Err(WizardError::WrongPhase(self.phase));
+        }
+        if suit == Suit::Suitless {
+            return Err(WizardError::InvalidTrumpSuit(suit));
+        }
+
+        if let Some(card) = self.trump.as_mut() {
+            card.suit = suit;
+        }
+        if let Some(round) = self.history.last_mut() {
+            round.trump_suit = suit;
+        }
+        self.emit(Event::TrumpSuitChosen {
+            seat: self.dealer(),
+            suit,
+        });
+        self.turn = self.leader;
+        self.phase = Phase::Betting;
+        self.update_legal_bets();
+        Ok(())
+    }
+
+    /// Place the bet of the player whose turn it is.
+    pub fn place_bet(&mut self, bet: u8) -> Result<(), WizardError> {
+        if self.phase != Phase::Betting {
+            return Err(WizardError::WrongPhase(self.phase));
+        }
+        if bet as usize > self.round_num {
+            return Err(WizardError::BetTooHigh {
+                bet,
+                max_bet: self.round_num as u8,
+            });
+        }
+        if !self.legal_bets().contains(&bet) {
+            return Err(WizardError::EvenBets(bet));
+        }
+
+        self.bets[self.turn.index()] = Some(bet);
+        self.emit(Event::BetPlaced {
+            seat: self.turn,
+            bet,
+        });
+        // Next to bet after those who have, the leader plays first once all have.
+        let placed = self.bets.iter().flatten().count();
+        match self.table.betting_order().nth(placed) {
+            Some(next) => self.turn = next,
+            None => {
+                let bets = self.bets.iter().flatten().copied().collect();
+                if let Some(round) = self.history.last_mut() {
+                    round.bets = bets;
+                }
+                self.turn = self.leader;
+                self.phase = Phase::Playing;
+            }
+        }
+        self.update_legal_bets();
+        Ok(())
+    }
+
+    /// Work out the bets the player whose turn it is may place.
+    fn update_legal_bets(&mut self) {
+        self.legal_bets.clear();
+        if self.phase != Phase::Betting {
+            return;
+        }
+        let max_bet = self.round_num as u8;
+        self.legal_bets.extend(0..=max_bet);
+
+        // Last player to bet can't make the total equal the tricks available.
+        if self.config.variants.uneven_bets && self.turn == self.dealer() {
+            let total: u8 = self.bets.iter().flatten().sum();
+            self.legal_bets.retain(|bet| total + bet != max_bet);
+        }
+    }
+
+    /// Play `card` from the hand of the player whose turn it is.
+    /// The copy of a Wizard or Jester with the same id is played if it's in
+    /// hand, otherwise any copy. An id that isn't `card`'s own is ignored.
+    pub fn play(&mut self, card: Card) -> Result<Option<Play>, WizardError> {
+        let hand = self.player(self.turn).hand;
+        let id = match hand.contains(card.id) && card.id.card() == card {
+            true => Some(card.id),
+            false => hand.cards().find(|c| *c == card).map(|c| c.id),
+        };
+        match id {
+            Some(id) => self.play_id(id),
+            None => Err(WizardError::CardNotInHand),
+        }
+    }
+
+    /// Play the card at `index` in the hand of the player whose turn it is,
+    /// counting in `CardId` order. Returns the winning play once the trick
+    /// is complete.
+    pub fn play_card(&mut self, index: usize) -> Result<Option<Play>, WizardError> {
+        match self.player(self.turn).hand.iter().nth(index) {
+            Some(id) => self.play_id(id),
+            None => Err(WizardError::CardNotInHand),
+        }
+    }
+
+    fn play_id(&mut self, id: CardId) -> Result<Option<Play>, WizardError> {
+        if self.phase != Phase::Playing {
+            return Err(WizardError::WrongPhase(self.phase));
+        }
+
+        let lead_suit = self.lead_suit();
+        let hand = &mut self.players[self.turn.index()].hand;
+        if !legal_card_set(*hand, lead_suit).contains(id) {
+            return Err(WizardError::MustFollowSuit(lead_suit));
+        }
+
+        hand.remove(id);
+        let card = id.card();
+        let play = Play {
+            card,
+            seat: self.turn,
+        };
+        self.trick.push(play);
+        self.emit(Event::CardPlayed(play));
+
+        // Next to play after the leader and those who followed, if anyone.
+        match self.table.play_order(self.leader).nth(self.trick.len()) {
+            Some(next) => {
+                self.turn = next;
+                Ok(None)
+            }
+            None => Ok(Some(self.finish_trick())),
+        }
+    }
+
+    /// The player with the highest score.
+    pub fn winner(&self) -> Option<PlayerId> {
+        calc_winner(&self.scores)
+    }
+
+    fn finish_trick(&mut self) -> Play {
+        let cards = self.trick.iter().map(|play| &play.card);
+        let index = calc_winner_of_trick(cards, self.trump_suit()).expect("trick is full");
+        let winning = self.trick[index];
+        let trick = Trick {
+            leader: self.leader,
+            lead_suit: self.lead_suit(),
+            plays: self.trick.drain(..).collect(),
+            winner: winning,
+        };
+        if let Some(round) = self.history.last_mut() {
+            round.tricks.push(trick);
+        }
+
+        self.tricks[winning.seat.index()] += 1;
+        self.emit(Event::TrickWon(winning));
+
+        // Winner of trick should lead next trick.
+        self.leader = winning.seat;
+        self.turn = self.leader;
+
+        if self.player(self.turn).hand.is_empty() {
+            let bets: Vec<u8> = self.bets.iter().flatten().copied().collect();
+            let points = calc_points(&bets, &self.tricks, self.scoring.as_ref());
+            for (score, points) in self.scores.iter_mut().zip(&points) {
+                *score += points;
+            }
+            let scores = self.scores.clone();
+            if let Some(round) = self.history.last_mut() {
+                round.tricks_won = self.tricks.clone();
+                round.points = points.clone();
+            }
+            self.emit(Event::RoundScored {
+                round_num: self.round_num,
+                points,
+                scores: scores.clone(),
+            });
+
+            self.phase = if self.round_num == self.config.num_rounds {
+                self.emit(Event::GameOver { scores });
+                Phase::GameOver
+            } else {
+                Phase::Dealing
+            };
+        }
+        winning
+    }
+
+    fn emit(&mut self, event: Event) {
+        for observer in self.observers.iter_mut() {
+            observer.notify(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::JESTER;
+#[cfg(test)]
+pub(crate) fn test_config(count: usize) -> crate::config::GameConfigBuilder {
+    (0..count).fold(GameConfig::builder(), |config, index| {
+        config.computer(&format!("P{}", index))
+    })
+}
+
+/// Deal and let the agents act until `done` returns true, checked before
+/// every move.
+#[cfg(test)]
+pub(crate) fn play_until(game: &mut GameState, mut done: impl FnMut(&GameState) -> bool) {
+    while !done(game) {
+        match game.phase() {
+            Phase::Dealing => game.deal().map(|_| ()).unwrap(),
+            _ => game.step().map(|_| ()).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn play_to_end(game: &mut GameState) {
+    play_until(game, |game| game.phase() == Phase::GameOver);
+}
+
+#[cfg(test)]
+fn round_over(game: &GameState) -> bool {
+    matches!(game.phase(), Phase::Dealing | Phase::GameOver)
+}
+
+#[test]
+pub fn test_invalid_config() {
+    let mut config = test_config(MAX_PLAYERS).build().unwrap();
+    config.players.push(config.players[0].clone());
+    assert_eq!(
+        GameState::new(config).err(),
+        Some(WizardError::PlayerCount(MAX_PLAYERS + 1))
+    );
+
+    let mut config = test_config(3).build().unwrap();
+    config.num_rounds = 21;
+    assert!(GameState::new(config).is_err());
+}
+
+#[test]
+pub fn test_deck_exhausted() {
+    let mut game = GameState::new(test_config(3).build().unwrap()).unwrap();
+    assert_eq!(
+        game.deal_from("Je Je".parse().unwrap()),
+        Err(WizardError::DeckExhausted)
+    );
+    assert_eq!(
+        game.deal_from(Deck(vec![JESTER; 3])),
+        Err(WizardError::DuplicateCard(JESTER))
+    );
+
+    // Nothing was dealt so the round can be dealt again.
+    assert_eq!(game.round_num(), 0);
+    assert_eq!(game.deal_from("Je Je Je".parse().unwrap()), Ok(None));
+    assert_eq!(game.round_num(), 1);
+}
+
+#[test]
+pub fn test_set_trump() {
+    use crate::components::card::Rank;
+
+    // Deck is dealt from the end, trump is flipped after three players get one card each.
+    let stacked = |trump: &str| format!("{} W Je W", trump).parse::<Deck>().unwrap();
+
+    // Normal card should be returned as trump.
+    let two_of_hearts = Card::new(Rank::Two, Suit::Heart);
+    let mut game = GameState::new(test_config(3).build().unwrap()).unwrap();
+    assert_eq!(game.deal_from(stacked("2H")), Ok(Some(two_of_hearts)));
+    assert_eq!(game.phase(), Phase::Betting);
+    assert_eq!(game.trump_suit(), Suit::Heart);
+
+    // Nothing special happens for Jester, returned like normal card.
+    let mut game = GameState::new(test_config(3).build().unwrap()).unwrap();
+    assert_eq!(game.deal_from(stacked("Je")), Ok(Some(JESTER)));
+    assert_eq!(game.phase(), Phase::Betting);
+    assert_eq!(game.trump_suit(), Suit::Suitless);
+
+    // If Wizard is flipped the dealer should choose a suit for trump.
+    let mut game = GameState::new(test_config(3).build().unwrap()).unwrap();
+    game.deal_from(stacked("W")).unwrap();
+    assert_eq!(game.phase(), Phase::ChoosingTrump);
+    assert_eq!(game.turn(), game.dealer());
+    assert_eq!(
+        game.place_bet(0),
+        Err(WizardError::WrongPhase(Phase::ChoosingTrump))
+    );
+    assert_eq!(
+        game.choose_trump_suit(Suit::Suitless),
+        Err(WizardError::InvalidTrumpSuit(Suit::Suitless))
+    );
+    game.choose_trump_suit(Suit::Spade).unwrap();
+    assert_eq!(game.trump().unwrap().rank, Rank::Wizard);
+    assert_eq!(game.trump_suit(), Suit::Spade);
+    assert_eq!(game.phase(), Phase::Betting);
+}
+
+#[test]
+pub fn test_play_round() {
+    use crate::components::card::Rank;
+
+    let card = Card::new;
+    let mut game = GameState::new(test_config(3).build().unwrap()).unwrap();
+
+    // Round 1: P0 deals, P1 leads and gets the first card.
+    game.deal_from(Deck(vec![
+        card(Rank::Two, Suit::Club),
+        JESTER,
+        card(Rank::Ace, Suit::Heart),
+        card(Rank::Five, Suit::Heart),
+    ]))
+    .unwrap();
+    assert_eq!(game.dealer(), PlayerId(0));
+    assert_eq!(game.turn(), PlayerId(1));
+    assert!(game.play_card(0).is_err());
+    game.place_bet(0).unwrap();
+    game.place_bet(1).unwrap();
+    game.place_bet(0).unwrap();
+    assert_eq!(game.phase(), Phase::Playing);
+
+    assert_eq!(game.play_card(0), Ok(None));
+    assert_eq!(game.play_card(0), Ok(None));
+    let winner = game.play_card(0).unwrap().unwrap();
+    assert_eq!(winner.seat, PlayerId(2));
+    assert_eq!(game.phase(), Phase::Dealing);
+
+    // Round 2: P1 deals, P2 leads. P2 holds Q♥ A♥, P0 holds K♣ 4♥, P1 holds 3♣ 2♥.
+    game.deal_from(Deck(vec![
+        card(Rank::Two, Suit::Spade),
+        card(Rank::Two, Suit::Heart),
+        card(Rank::Four, Suit::Heart),
+        card(Rank::Ace, Suit::Heart),
+        card(Rank::Three, Suit::Club),
+        card(Rank::King, Suit::Club),
+        card(Rank::Queen, Suit::Heart),
+    ]))
+    .unwrap();
+    assert_eq!(game.dealer(), PlayerId(1));
+    assert_eq!(game.turn(), PlayerId(2));
+    assert_eq!(game.trump_suit(), Suit::Spade);
+    assert_eq!(
+        game.place_bet(3),
+        Err(WizardError::BetTooHigh { bet: 3, max_bet: 2 })
+    );
+    game.place_bet(2).unwrap();
+    game.place_bet(0).unwrap();
+    game.place_bet(1).unwrap();
+
+    assert_eq!(game.play_card(1), Ok(None));
+    assert_eq!(game.lead_suit(), Suit::Heart);
+    assert_eq!(
+        game.play_card(0),
+        Err(WizardError::MustFollowSuit(Suit::Heart))
+    );
+    assert_eq!(game.play_card(1), Ok(None));
+    assert_eq!(
+        game.play_card(0),
+        Err(WizardError::MustFollowSuit(Suit::Heart))
+    );
+    let winner = game.play_card(1).unwrap().unwrap();
+    assert_eq!(winner.seat, PlayerId(2));
+    assert_eq!(game.turn(), PlayerId(2));
+
+    // Nobody else holds hearts so the lead wins.
+    game.play_card(0).unwrap();
+    game.play_card(0).unwrap();
+    let winner = game.play_card(0).unwrap().unwrap();
+    assert_eq!(winner.seat, PlayerId(2));
+    assert_eq!(game.phase(), Phase::Dealing);
+
+    let scores: Vec<i16> = game.scores().to_vec();
+    assert_eq!(scores, vec![40, 10, 70]);
+}
+
+#[test]
+pub fn test_play_copy_of_card() {
+    use crate::components::card::Rank;
+
+    let mut game = GameState::new(test_config(3).build().unwrap()).unwrap();
+    game.deal_from("2S 3C 4C 5C".parse().unwrap()).unwrap();
+    play_until(&mut game, |game| game.phase() == Phase::Dealing);
+
+    // P2 leads holding both W and W2, and plays the second one.
+    game.deal_from("2S 3C 4C W2 5C 6C W".parse().unwrap())
+        .unwrap();
+    while game.phase() == Phase::Betting {
+        game.place_bet(0).unwrap();
+    }
+    let second: Card = "W2".parse().unwrap();
+    // An id borrowed from another card doesn't play the card it belongs to.
+    let forged = Card {
+        id: second.id,
+        ..Card::new(Rank::Two, Suit::Spade)
+    };
+    assert_eq!(game.play(forged), Err(WizardError::CardNotInHand));
+    game.play(second).unwrap();
+    assert_eq!(game.trick()[0].card.id, second.id);
+    let hand: Vec<CardId> = game.player(PlayerId(2)).hand.iter().collect();
+    assert_eq!(hand, vec![WIZARD.id]);
+}
+
+#[test]
+pub fn test_lead_suit_in_trick() {
+    use crate::components::card::Rank;
+
+    let card = Card::new;
+    let mut game = GameState::new(test_config(4).build().unwrap()).unwrap();
+
+    // Round 1: P1 and P2 lead Jesters, P3's 5♥ sets the suit for P0.
+    game.deal_from("2C AH 5H Je Je".parse().unwrap()).unwrap();
+    for bet in [0, 0, 0, 0] {
+        game.place_bet(bet).unwrap();
+    }
+    assert_eq!(game.lead_suit(), Suit::Suitless);
+    game.play_card(0).unwrap();
+    assert_eq!(game.view(game.turn()).lead_suit, Suit::Suitless);
+    game.play_card(0).unwrap();
+    assert_eq!(game.view(game.turn()).lead_suit, Suit::Suitless);
+    game.play_card(0).unwrap();
+    assert_eq!(game.view(game.turn()).lead_suit, Suit::Heart);
+    let winner = game.play_card(0).unwrap().unwrap();
+    assert_eq!(winner.seat, PlayerId(0));
+
+    // Round 2: P2 holds W 2♠, P3 3♥ 4♣, P0 5♦ 6♦, P1 7♦ 8♦.
+    game.deal_from(Deck(vec![
+        card(Rank::Two, Suit::Club),
+        card(Rank::Eight, Suit::Diamond),
+        card(Rank::Six, Suit::Diamond),
+        card(Rank::Four, Suit::Club),
+        card(Rank::Two, Suit::Spade),
+        card(Rank::Seven, Suit::Diamond),
+        card(Rank::Five, Suit::Diamond),
+        card(Rank::Three, Suit::Heart),
+        WIZARD,
+    ]))
+    .unwrap();
+    for bet in [1, 0, 0, 0] {
+        game.place_bet(bet).unwrap();
+    }
+
+    // After a Wizard lead anything may be played, suited cards don't change that.
+    game.play(WIZARD).unwrap();
+    assert_eq!(game.lead_suit(), Suit::Suitless);
+    game.play_card(1).unwrap();
+    assert_eq!(game.view(game.turn()).lead_suit, Suit::Suitless);
+    game.play_card(0).unwrap();
+    let winner = game.play_card(0).unwrap().unwrap();
+    assert_eq!(winner.seat, PlayerId(2));
+
+    // The next trick starts fresh rather than from the last one's cards.
+    assert_eq!(game.lead_suit(), Suit::Suitless);
+    game.play_card(0).unwrap();
+    assert_eq!(game.view(game.turn()).lead_suit, Suit::Spade);
+    assert_eq!(game.play_card(0), Ok(None));
+}
+
+#[test]
+pub fn test_step_plays_full_game() {
+    let mut game = GameState::new(test_config(3).build().unwrap()).unwrap();
+    assert!(game.step().is_err());
+
+    while game.phase() != Phase::GameOver {
+        game.deal().unwrap();
+        assert_eq!(game.dealer(), PlayerId((game.round_num() - 1) % 3));
+        assert_eq!(game.bets(), vec![None; 3]);
+        play_until(&mut game, round_over);
+        assert!(game.bets().iter().all(|bet| bet.is_some()));
+    }
+
+    assert_eq!(game.round_num(), 20);
+    assert!(game.players().iter().all(|player| player.hand.is_empty()));
+}
+
+#[test]
+pub fn test_same_seed_same_game() {
+    let play_game = |seed| {
+        let mut game = GameState::new(test_config(4).seed(seed).build().unwrap()).unwrap();
+        let mut trumps = Vec::new();
+        while game.phase() != Phase::GameOver {
+            trumps.push(game.deal().unwrap());
+            play_until(&mut game, round_over);
+        }
+        (trumps, game.scores().to_vec())
+    };
+
+    assert_eq!(play_game(42), play_game(42));
+    assert_ne!(play_game(42).0, play_game(43).0);
+}
+
+#[test]
+pub fn test_uneven_bets() {
+    use crate::config::RuleVariants;
+
+    let config = test_config(3)
+        .num_rounds(2)
+        .variants(RuleVariants { uneven_bets: true })
+        .build()
+        .unwrap();
+    let mut game = GameState::new(config).unwrap();
+    assert_eq!(game.num_rounds(), 2);
+
+    game.deal_from("Je Je Je Je".parse().unwrap()).unwrap();
+    game.place_bet(1).unwrap();
+    assert_eq!(game.legal_bets(), vec![0, 1]);
+    game.place_bet(0).unwrap();
+
+    // Dealer bets last and can't make the bets add up to 1.
+    assert_eq!(game.legal_bets(), vec![1]);
+    assert_eq!(game.view(game.dealer()).legal_bets, vec![1]);
+    assert!(game.view(PlayerId(1)).legal_bets.is_empty());
+    assert_eq!(game.place_bet(0), Err(WizardError::EvenBets(0)));
+    game.place_bet(1).unwrap();
+    assert_eq!(game.phase(), Phase::Playing);
+
+    // Bots respect it too.
+    play_until(&mut game, |game| {
+        if game.phase() == Phase::Betting && game.turn() == game.dealer() {
+            let total: u8 = game.bets().iter().flatten().sum();
+            if let Some(even_bet) = 2u8.checked_sub(total) {
+                assert!(!game.legal_bets().contains(&even_bet));
+            }
+        }
+        game.phase() == Phase::GameOver
+    });
+}
+
+#[test]
+pub fn test_observer_events() {
+    use std::sync::mpsc;
+
+    let mut game = GameState::new(test_config(3).num_rounds(1).build().unwrap()).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    game.add_observer(Box::new(sender));
+
+    game.deal_from("Je Je2 W Je3".parse().unwrap()).unwrap();
+    game.place_bet(0).unwrap();
+    game.place_bet(1).unwrap();
+    game.place_bet(0).unwrap();
+    let play = |seat, card| Play {
+        card,
+        seat: PlayerId(seat),
+    };
+    game.play_card(0).unwrap();
+    game.play_card(0).unwrap();
+    game.play_card(0).unwrap();
+
+    let events: Vec<Event> = receiver.try_iter().collect();
+    assert_eq!(
+        events,
+        vec![
+            Event::RoundStarted {
+                round_num: 1,
+                dealer: PlayerId(0),
+                leader: PlayerId(1)
+            },
+            Event::CardsDealt {
+                seat: PlayerId(0),
+                hand: "Je2".parse::<Deck>().unwrap().into()
+            },
+            Event::CardsDealt {
+                seat: PlayerId(1),
+                hand: "Je3".parse::<Deck>().unwrap().into()
+            },
+            Event::CardsDealt {
+                seat: PlayerId(2),
+                hand: Deck(vec![WIZARD]).into()
+            },
+            Event::TrumpRevealed(Some(JESTER)),
+            Event::BetPlaced {
+                seat: PlayerId(1),
+                bet: 0
+            },
+            Event::BetPlaced {
+                seat: PlayerId(2),
+                bet: 1
+            },
+            Event::BetPlaced {
+                seat: PlayerId(0),
+                bet: 0
+            },
+            Event::CardPlayed(play(1, JESTER)),
+            Event::CardPlayed(play(2, WIZARD)),
+            Event::CardPlayed(play(0, JESTER)),
+            Event::TrickWon(play(2, WIZARD)),
+            Event::RoundScored {
+                round_num: 1,
+                points: vec![20, 20, 30],
+                scores: vec![20, 20, 30]
+            },
+            Event::GameOver {
+                scores: vec![20, 20, 30]
+            },
+        ]
+    );
+}
+
+#[test]
+pub fn test_history() {
+    use crate::components::card::Rank;
+
+    let card = Card::new;
+    let mut game = GameState::new(test_config(3).num_rounds(2).build().unwrap()).unwrap();
+    assert!(game.history().is_empty());
+
+    // Round 1: P1 leads with 5♥, P2 plays a Jester and P0 wins with A♥.
+    game.deal_from(Deck(vec![
+        WIZARD,
+        card(Rank::Ace, Suit::Heart),
+        JESTER,
+        card(Rank::Five, Suit::Heart),
+    ]))
+    .unwrap();
+    game.choose_trump_suit(Suit::Club).unwrap();
+    game.place_bet(0).unwrap();
+    assert!(game.history()[0].bets.is_empty());
+    game.place_bet(0).unwrap();
+    game.place_bet(1).unwrap();
+    game.play_card(0).unwrap();
+    game.play_card(0).unwrap();
+    assert_eq!(game.played().len(), 2);
+    game.play_card(0).unwrap();
+    assert_eq!(game.played().len(), 3);
+
+    // Round 2 is in progress and doesn't change round 1.
+    game.deal().unwrap();
+    assert_eq!(game.history().len(), 2);
+    assert!(!game.history()[1].is_scored());
+    assert!(game.played().is_empty());
+
+    let round = &game.history()[0];
+    assert_eq!(round.dealer, PlayerId(0));
+    assert_eq!(round.trump, Some(WIZARD));
+    assert_eq!(round.trump_suit, Suit::Club);
+    assert_eq!(round.bets, vec![1, 0, 0]);
+    assert_eq!(round.tricks_won, vec![1, 0, 0]);
+    assert_eq!(round.points, vec![30, 20, 20]);
+    assert!(round.is_scored());
+
+    let play = |seat, card| Play {
+        card,
+        seat: PlayerId(seat),
+    };
+    assert_eq!(
+        round.tricks,
+        vec![Trick {
+            leader: PlayerId(1),
+            plays: vec![
+                play(1, card(Rank::Five, Suit::Heart)),
+                play(2, JESTER),
+                play(0, card(Rank::Ace, Suit::Heart)),
+            ],
+            lead_suit: Suit::Heart,
+            winner: play(0, card(Rank::Ace, Suit::Heart)),
+        }]
+    );
+    assert_eq!(round.plays().count(), 3);
+}
+
+/// Game with seed 3 stopped two cards into a trick of round 3, and its save.
+#[cfg(all(test, feature = "serde"))]
+fn saved_mid_trick() -> (GameState, String) {
+    let mut game = GameState::new(test_config(4).seed(3).build().unwrap()).unwrap();
+    play_until(&mut game, |game| {
+        game.round_num() >= 3 && game.trick().len() >= 2
+    });
+    let json = serde_json::to_string(&game.save()).unwrap();
+    (game, json)
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn test_save_and_resume() {
+    let (mut game, json) = saved_mid_trick();
+    let mut resumed = GameState::resume(serde_json::from_str(&json).unwrap()).unwrap();
+    assert_eq!(resumed.phase(), Phase::Playing);
+    assert_eq!(resumed.turn(), game.turn());
+    assert_eq!(resumed.trick(), game.trick());
+    assert_eq!(resumed.history(), game.history());
+
+    // Both games play out the same from here.
+    play_to_end(&mut game);
+    play_to_end(&mut resumed);
+    assert_eq!(resumed.history(), game.history());
+    assert_eq!(resumed.scores(), game.scores());
+
+    // Saving left the game as it was, it went the same as one never saved.
+    let mut unsaved = GameState::new(test_config(4).seed(3).build().unwrap()).unwrap();
+    play_to_end(&mut unsaved);
+    assert_eq!(unsaved.history(), game.history());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn test_resume_corrupted_save() {
+    let (_, json) = saved_mid_trick();
+    let corrupt = |json: &str, edit: fn(&mut SavedGame)| {
+        let mut saved: SavedGame = serde_json::from_str(json).unwrap();
+        edit(&mut saved);
+        match GameState::resume(saved) {
+            Err(WizardError::SaveFile(_)) => {}
+            other => panic!("resumed a corrupted save: {:?}", other.map(|_| ())),
+        }
+    };
+
+    corrupt(&json, |saved| saved.turn = PlayerId(99));
+    corrupt(&json, |saved| saved.leader = PlayerId(4));
+    corrupt(&json, |saved| saved.round_num = 99);
+    corrupt(&json, |saved| saved.phase = Phase::Betting);
+    corrupt(&json, |saved| saved.phase = Phase::Dealing);
+    corrupt(&json, |saved| {
+        let hand = &mut saved.players[0].hand;
+        hand.remove(hand.iter().next().unwrap());
+    });
+    corrupt(&json, |saved| {
+        let card = saved.trick[0];
+        saved.trick.extend([card, card, card]);
+    });
+    corrupt(&json, |saved| saved.turn = saved.leader);
+    corrupt(&json, |saved| {
+        let card = saved.players[0].hand.iter().next().unwrap();
+        let hand = &mut saved.players[1].hand;
+        hand.remove(hand.iter().next().unwrap());
+        hand.insert(card);
+    });
+    corrupt(&json, |saved| {
+        let card = saved.players[0].hand.iter().next().unwrap();
+        saved.deck.push(card.card());
+    });
+
+    // One bet placed in the first round.
+    let mut game = GameState::new(test_config(4).seed(3).build().unwrap()).unwrap();
+    game.deal().unwrap();
+    game.step().unwrap();
+    let json = serde_json::to_string(&game.save()).unwrap();
+    assert!(GameState::resume(serde_json::from_str(&json).unwrap()).is_ok());
+    corrupt(&json, |saved| saved.bets[saved.turn.index()] = Some(1));
+    corrupt(&json, |saved| saved.phase = Phase::ChoosingTrump);
+}
@@ -0,0 +1,48 @@
+use crate::components::card::{Card, Suit};
+use crate::table::PlayerId;
+use crate::Play;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A completed trick.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Trick {
+    pub leader: PlayerId,
+    /// Cards in the order they were played.
+    pub plays: Vec<Play>,
+    /// Suit of the first non-Jester card, Suitless if a Wizard was led.
+    pub lead_suit: Suit,
+    pub winner: Play,
+}
+
+/// Record of one round. The round being played is kept up to date as it
+/// goes: bets are filled in once betting is over, tricks as they complete,
+/// and the tricks won and points once it has been scored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Round {
+    pub round_num: usize,
+    pub dealer: PlayerId,
+    /// The card flipped after dealing, None in the last round.
+    pub trump: Option<Card>,
+    /// Suit of the trump card, or the suit picked by the dealer for a Wizard.
+    pub trump_suit: Suit,
+    /// Bets by seat, empty until every player has bet.
+    pub bets: Vec<u8>,
+    pub tricks: Vec<Trick>,
+    /// Tricks won by seat, empty until the round is scored.
+    pub tricks_won: Vec<u8>,
+    /// Points earned this round by seat, empty until the round is scored.
+    pub points: Vec<i16>,
+}
+impl Round {
+    pub fn is_scored(&self) -> bool {
+        !self.points.is_empty()
+    }
+
+    /// Every card played this round in order.
+    pub fn plays(&self) -> impl Iterator<Item = &Play> {
+        self.tricks.iter().flat_map(|trick| trick.plays.iter())
+    }
+}
@@ -1,515 +1,320 @@
+pub mod advisor;
+pub mod agents;
 pub mod components;
+pub mod config;
+pub mod error;
+pub mod events;
+pub mod game;
+pub mod history;
+#[cfg(feature = "serde")]
+pub mod save;
+pub mod scoring;
+pub mod table;
+pub mod tracker;
 
 use crate::components::card::*;
-use crate::components::player::*;
-use crate::components::util::Util;
-use rand::Rng;
-
-pub const WIZARD: Card = Card {
-    rank: Rank::Wizard,
-    suit: Suit::Suitless,
-};
-pub const JESTER: Card = Card {
-    rank: Rank::Jester,
-    suit: Suit::Suitless,
-};
-
-pub fn get_players() -> Vec<Player> {
-    println!("Enter your name:");
-    let name = Util::cli_next_string();
-
-    let mut players = vec![Player {
-        name,
-        operator: Operator::Human,
-        ..Player::new()
-    }];
-
-    let computer_names = ["Merlin", "Oz", "Sarumon", "Gandalf", "Kvothe"];
-    for (index, name) in computer_names.iter().enumerate() {
-        players.push(Player {
-            name: String::from(*name),
-            original_position: index + 1,
-            ..Player::new()
-        });
-    }
-
-    Util::shuffle_vec(players)
-}
-
-pub fn set_trump(mut card: Card, dealer: &Player) -> Card {
-    println!(" Trump: {}", card);
-
-    if card == WIZARD {
-        println!("\nTrump is a Wizard!");
-        let suits = [Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade];
-
-        match dealer.operator {
-            Operator::Human => {
-                println!("Which suit do you select as trump?");
-                for i in 0..suits.len() {
-                    println!("  {}. {}", i + 1, suits[i].symbol());
-                }
-
-                loop {
-                    let selection = Util::cli_next_pos_num() as usize - 1;
-                    if selection > suits.len() {
-                        println!("Hey! Gotta pick what's offered here!");
-                        continue;
-                    }
-
-                    card.suit = suits[selection];
-                    break;
-                }
-            }
-            Operator::Computer => {
-                println!("{} will select suit...", dealer.name);
-                Util::sleep();
-                let rand_index = rand::thread_rng().gen_range(0..3);
-                card.suit = suits[rand_index];
-            }
-        };
-
-        println!("\n Trump suit: {}", card.suit.symbol());
-    }
-    card
+use crate::components::card_set::CardSet;
+use crate::components::deck::Deck;
+use crate::scoring::ScoringRule;
+use crate::table::PlayerId;
+use rand::{Rng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const WIZARD: Card = Card::new(Rank::Wizard, Suit::Suitless);
+pub const JESTER: Card = Card::new(Rank::Jester, Suit::Suitless);
+
+pub const MIN_PLAYERS: usize = 3;
+pub const MAX_PLAYERS: usize = 6;
+
+/// Rounds in a game: the whole deck is dealt out in the last round.
+pub fn num_rounds(num_players: usize) -> usize {
+    Deck::build().len() / num_players
 }
 
 #[test]
-pub fn test_set_trump() {
-    let dealer = Player::new();
-
-    // Normal card should be returned as trump.
-    let two_of_hearts = Card {
-        rank: Rank::Two,
-        suit: Suit::Heart,
-    };
-    let mut trump = set_trump(two_of_hearts, &dealer);
-    assert_eq!(trump, two_of_hearts);
-
-    // Nothing special happens for Jester, returned like normal card.
-    trump = set_trump(JESTER, &dealer);
-    assert_eq!(trump, JESTER);
-
-    // If Wizard is flipped the dealer should choose a suit for trump.
-    trump = set_trump(WIZARD, &dealer);
-    assert_eq!(trump.rank, Rank::Wizard);
-    assert_ne!(trump.suit, Suit::Suitless);
+pub fn test_num_rounds() {
+    assert_eq!(num_rounds(3), 20);
+    assert_eq!(num_rounds(4), 15);
+    assert_eq!(num_rounds(5), 12);
+    assert_eq!(num_rounds(6), 10);
 }
 
-pub fn place_bets(players: &mut Vec<Player>) {
-    for player in players.iter_mut() {
-        let max_bet = player.hand.len();
-
-        match player.operator {
-            Operator::Human => {
-                println!("What is your bet?");
-
-                loop {
-                    player.bet = Util::cli_next_num();
-                    if player.bet > max_bet as u8 {
-                        println!(
-                            "Yer a cocky one eh?\nBet must be in the range of 0 to {}.",
-                            max_bet
-                        );
-                        continue;
-                    }
-                    break;
-                }
-            }
-            Operator::Computer => {
-                Util::sleep();
-                player.bet = rand::thread_rng().gen_range(0..(max_bet + 1)) as u8;
-            }
-        };
-
-        println!("{:>8} bet {}", player.name, player.bet);
-    }
+pub fn get_trump_from_comp(rng: &mut dyn RngCore) -> Suit {
+    let rand_index = rng.gen_range(0..Suit::ALL.len());
+    Suit::ALL[rand_index]
 }
 
-pub fn play_tricks(mut players: &mut Vec<Player>, trump: Card) {
-    for trick_num in 1..(players[0].hand.len() + 1) {
-        println!("======= Trick #{} =======", trick_num);
-        let mut lead_suit = Suit::Suitless;
-
-        for player in players.clone() {
-            if player.card_played != JESTER {
-                lead_suit = player.card_played.suit.clone();
-                break;
-            }
-        }
-
-        players.iter_mut().for_each(|mut player: &mut Player| {
-            match player.operator {
-                Operator::Human => get_play_from_human(&mut player, lead_suit),
-                Operator::Computer => get_play_from_comp(&mut player, lead_suit),
-            };
+pub fn get_bet_from_comp(legal_bets: &[u8], rng: &mut dyn RngCore) -> u8 {
+    legal_bets[rng.gen_range(0..legal_bets.len())]
+}
 
-            println!("{:>8}: {}", player.name, player.card_played);
-        });
+/// Returns the index in `hand` of the card the computer will play, None if
+/// `hand` is empty.
+pub fn get_play_from_comp(hand: &Deck, lead_suit: Suit) -> Option<usize> {
+    let legal = legal_cards(hand, lead_suit);
+
+    // Play first legal card in hand if suit cannot be followed.
+    let card = legal
+        .iter()
+        .find(|card| card.suit == lead_suit)
+        .or_else(|| legal.first())?;
+    hand.iter().position(|c| c == card)
+}
 
-        calc_winner_of_trick(&mut players, trump.suit);
+#[test]
+pub fn test_get_play_from_comp() {
+    let hand: Deck = "W 2C 5H".parse().unwrap();
+    assert_eq!(get_play_from_comp(&hand, Suit::Heart), Some(2));
+    assert_eq!(get_play_from_comp(&hand, Suit::Spade), Some(0));
+    assert_eq!(get_play_from_comp(&Deck(Vec::new()), Suit::Heart), None);
+}
 
-        Util::press_enter_to_("play next trick");
-    }
+/// Cards in `hand` that may be played into a trick with `lead_suit`.
+/// Wizards and Jesters can always be played. Lead suit must be followed if
+/// possible, unless nothing has been led yet or a Wizard was led (Suitless).
+pub fn legal_cards(hand: &[Card], lead_suit: Suit) -> Vec<Card> {
+    let legal = legal_card_set(CardSet::from(hand), lead_suit);
+    hand.iter()
+        .filter(|card| legal.contains(card.id))
+        .copied()
+        .collect()
 }
 
-pub fn get_play_from_human(player: &mut Player, lead_suit: Suit) {
-    println!("\nYour hand:");
-    for (index, card) in player.hand.iter().enumerate() {
-        println!("  {}. {}", index + 1, card);
+/// `legal_cards` for a hand held as a `CardSet`, without allocating.
+pub fn legal_card_set(hand: CardSet, lead_suit: Suit) -> CardSet {
+    let follow_suit = hand.of_suit(lead_suit);
+    if follow_suit.is_empty() {
+        return hand;
     }
+    follow_suit | (hand & (CardSet::WIZARDS | CardSet::JESTERS))
+}
 
-    let mut can_follow_suit = false;
+#[test]
+pub fn test_legal_cards() {
+    let card = Card::new;
+    let hand = vec![
+        card(Rank::Two, Suit::Heart),
+        WIZARD,
+        card(Rank::King, Suit::Club),
+        JESTER,
+        card(Rank::Ace, Suit::Heart),
+    ];
 
-    // Not Suit::Suitless is needed because lead_suit is initialized as Suit::Suitless and it's possible a Wizard or Jester could match here.
-    if lead_suit != Suit::Suitless {
-        for card in player.hand.iter() {
-            if card.suit == lead_suit {
-                can_follow_suit = true;
-                break;
-            }
-        }
-    }
+    // Anything can be led.
+    assert_eq!(legal_cards(&hand, Suit::Suitless), hand);
 
-    println!("Which card will you play?");
+    // Must follow suit, but Wizards and Jesters are always allowed.
+    assert_eq!(
+        legal_cards(&hand, Suit::Heart),
+        vec![hand[0], WIZARD, JESTER, hand[4]]
+    );
 
-    loop {
-        let selection = Util::cli_next_pos_num() as usize - 1;
+    // Can't follow suit so anything goes.
+    assert_eq!(legal_cards(&hand, Suit::Spade), hand);
 
-        if selection >= player.hand.len() {
-            println!("Hey! Gotta pick what's offered!");
-            continue;
-        }
-
-        let card = player
-            .hand
-            .drain(selection..(selection + 1))
-            .last()
-            .unwrap();
-
-        if can_follow_suit {
-            if card.suit != lead_suit {
-                println!("Hey! Gotta follow suit!");
-                player.hand.insert(selection, card);
-                continue;
-            }
-        }
+    // Only Wizards and Jesters left is fine too.
+    assert_eq!(
+        legal_cards(&[WIZARD, JESTER], Suit::Heart),
+        vec![WIZARD, JESTER]
+    );
 
-        player.card_played = card;
-        return;
-    }
+    // Same rules for a set.
+    let set = CardSet::from(hand.as_slice());
+    assert_eq!(
+        legal_card_set(set, Suit::Heart),
+        CardSet::from(&legal_cards(&hand, Suit::Heart)[..])
+    );
+    assert_eq!(legal_card_set(set, Suit::Spade), set);
 }
 
-pub fn get_play_from_comp(player: &mut Player, lead_suit: Suit) {
-    Util::sleep();
-
-    // Play first card in hand if suit cannot be followed.
-    let mut selected = 0;
-    for (index, card) in player.hand.iter().enumerate() {
-        if card.suit == lead_suit {
-            selected = index;
-            break;
-        }
+/// Suit that must be followed in a trick: the suit of the first card that
+/// isn't a Jester. Suitless if nothing but Jesters were played or a Wizard led.
+pub fn lead_suit<'a>(cards: impl IntoIterator<Item = &'a Card>) -> Suit {
+    match cards.into_iter().find(|card| **card != JESTER) {
+        Some(card) => card.suit,
+        None => Suit::Suitless,
     }
+}
 
-    let card = player.hand.drain(selected..(selected + 1)).last().unwrap();
-    player.card_played = card;
+#[test]
+pub fn test_lead_suit() {
+    let cards = |s: &str| s.parse::<Deck>().unwrap();
+    assert_eq!(lead_suit(&[]), Suit::Suitless);
+    assert_eq!(lead_suit(&cards("5H 2S")), Suit::Heart);
+    // A Wizard lead leaves the trick open, even once suited cards follow.
+    assert_eq!(lead_suit(&cards("W 5H 2S")), Suit::Suitless);
+    // Jesters don't set the suit, the first card after them does.
+    assert_eq!(lead_suit(&cards("Je")), Suit::Suitless);
+    assert_eq!(lead_suit(&cards("Je 5H 2S")), Suit::Heart);
+    assert_eq!(lead_suit(&cards("Je Je2 Je3")), Suit::Suitless);
+    assert_eq!(lead_suit(&cards("Je Je2 2S")), Suit::Spade);
+    // A Wizard after Jesters leaves it open as well.
+    assert_eq!(lead_suit(&cards("Je W 5H")), Suit::Suitless);
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Play {
-    card: Card,
-    player_index: usize,
+    pub card: Card,
+    pub seat: PlayerId,
 }
 
-pub fn calc_winner_of_trick(players: &mut Vec<Player>, trump_suit: Suit) {
-    let mut winning = Play {
-        card: players[0].card_played.clone(),
-        player_index: 0,
-    };
-    let mut lead_suit = winning.card.suit;
-
-    for (index, current) in players.iter().enumerate() {
-        if current.card_played == WIZARD {
-            winning.card = current.card_played.clone();
-            winning.player_index = index;
+/// Returns the index in `cards` of the card that wins the trick, None if no
+/// cards were played. Cards must be in the order they were played.
+pub fn calc_winner_of_trick<'a>(
+    cards: impl IntoIterator<Item = &'a Card>,
+    trump_suit: Suit,
+) -> Option<usize> {
+    let mut cards = cards.into_iter().peekable();
+    let mut winning = **cards.peek()?;
+    let mut winner = 0;
+    let mut lead_suit = winning.suit;
+
+    for (index, current) in cards.enumerate() {
+        if *current == WIZARD {
+            winner = index;
             break;
         }
 
-        if current.card_played == JESTER {
+        if *current == JESTER {
             continue;
         }
 
         // If Jester was led take suit from first non-Jester.
-        if winning.card == JESTER {
-            if current.card_played != JESTER {
-                winning.card = current.card_played.clone();
-                winning.player_index = index;
-                lead_suit = winning.card.suit;
-                continue;
-            }
+        if winning == JESTER {
+            winning = *current;
+            winner = index;
+            lead_suit = winning.suit;
+            continue;
         }
 
-        if winning.card.suit == trump_suit {
-            if current.card_played.suit == trump_suit {
-                if current.card_played.rank.value() > winning.card.rank.value() {
-                    winning.card = current.card_played.clone();
-                    winning.player_index = index;
-                    continue;
-                }
+        if winning.suit == trump_suit {
+            if current.suit == trump_suit && current.rank.value() > winning.rank.value() {
+                winning = *current;
+                winner = index;
             }
             continue;
         }
 
-        if current.card_played.suit == trump_suit {
-            winning.card = current.card_played.clone();
-            winning.player_index = index;
+        if current.suit == trump_suit {
+            winning = *current;
+            winner = index;
             continue;
         }
 
         // Follow suit...
-        if current.card_played.suit == lead_suit {
-            if current.card_played.rank.value() > winning.card.rank.value() {
-                winning.card = current.card_played.clone();
-                winning.player_index = index;
-            }
+        if current.suit == lead_suit && current.rank.value() > winning.rank.value() {
+            winning = *current;
+            winner = index;
         }
     }
 
-    players[winning.player_index].tricks += 1;
-    let winner = players[winning.player_index].clone();
-    println!(
-        "\n  Winner: {} - {}\n========================",
-        winner.card_played, winner.name
-    );
-
-    // Winner of trick should lead next trick.
-    players.rotate_left(winner.original_position);
+    Some(winner)
 }
 
 #[test]
 pub fn test_calc_trick() {
-    // Test all non-trump, no Wizard or Jester.
-    let mut players = vec![
-        Player {
-            card_played: Card {
-                rank: Rank::Two,
-                suit: Suit::Heart,
-            },
-            ..Player::new()
-        },
-        Player {
-            card_played: Card {
-                rank: Rank::King,
-                suit: Suit::Heart,
-            },
-            ..Player::new()
-        },
-        Player {
-            card_played: Card {
-                rank: Rank::Ace,
-                suit: Suit::Diamond,
-            },
-            ..Player::new()
-        },
-    ];
+    let trick = |cards: &str| cards.parse::<Deck>().unwrap().0;
 
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[1].tricks, 1);
+    // Test all non-trump, no Wizard or Jester.
+    assert_eq!(
+        calc_winner_of_trick(&trick("2H KH AD"), Suit::Spade),
+        Some(1)
+    );
 
     // Ace of lead suit should now win.
-    players.push(Player {
-        card_played: Card {
-            rank: Rank::Ace,
-            suit: Suit::Heart,
-        },
-        ..Player::new()
-    });
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[3].tricks, 1);
+    assert_eq!(
+        calc_winner_of_trick(&trick("2H KH AD AH"), Suit::Spade),
+        Some(3)
+    );
 
     // Low trump should now win.
-    players.push(Player {
-        card_played: Card {
-            rank: Rank::Two,
-            suit: Suit::Spade,
-        },
-        ..Player::new()
-    });
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[4].tricks, 1);
+    assert_eq!(
+        calc_winner_of_trick(&trick("2H KH AD AH 2S"), Suit::Spade),
+        Some(4)
+    );
 
     // Higher Trump should now win.
-    players.push(Player {
-        card_played: Card {
-            rank: Rank::Ace,
-            suit: Suit::Spade,
-        },
-        ..Player::new()
-    });
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[5].tricks, 1);
+    assert_eq!(
+        calc_winner_of_trick(&trick("2H KH AD AH 2S AS"), Suit::Spade),
+        Some(5)
+    );
 
     // First Wizard always wins.
-    players.clear();
-    for _ in 0..3 {
-        players.push(Player {
-            card_played: WIZARD,
-            ..Player::new()
-        });
-    }
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[0].tricks, 1);
+    assert_eq!(calc_winner_of_trick(&trick("W W W"), Suit::Spade), Some(0));
 
     // First Jester wins if all Jesters.
-    players.clear();
-    for _ in 0..3 {
-        players.push(Player {
-            card_played: JESTER,
-            ..Player::new()
-        });
-    }
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[0].tricks, 1);
+    assert_eq!(
+        calc_winner_of_trick(&trick("Je Je Je"), Suit::Spade),
+        Some(0)
+    );
 
     // First non-Jester sets lead suit.
-    players.push(Player {
-        card_played: Card {
-            rank: Rank::Two,
-            suit: Suit::Diamond,
-        },
-        ..Player::new()
-    });
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[3].tricks, 1);
+    assert_eq!(
+        calc_winner_of_trick(&trick("Je Je Je 2D"), Suit::Spade),
+        Some(3)
+    );
 
     // New lead suit is now followed.
-    players.push(Player {
-        card_played: Card {
-            rank: Rank::Ace,
-            suit: Suit::Diamond,
-        },
-        ..Player::new()
-    });
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[4].tricks, 1);
+    assert_eq!(
+        calc_winner_of_trick(&trick("Je Je Je 2D AD"), Suit::Spade),
+        Some(4)
+    );
 
     // Trump still wins.
-    players.push(Player {
-        card_played: Card {
-            rank: Rank::Two,
-            suit: Suit::Spade,
-        },
-        ..Player::new()
-    });
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[5].tricks, 1);
+    let cards = trick("Je Je Je 2D AD 2S");
+    assert_eq!(calc_winner_of_trick(&cards, Suit::Spade), Some(5));
 
     // If there is no trump then highest lead suit wins.
-    calc_winner_of_trick(&mut players, Suit::Suitless);
-    assert_eq!(players[4].tricks, 2);
+    assert_eq!(calc_winner_of_trick(&cards, Suit::Suitless), Some(4));
 
     // Make sure second Jester doesn't mess up lead suit.
-    players = vec![
-        Player {
-            card_played: JESTER,
-            ..Player::new()
-        },
-        Player {
-            card_played: Card {
-                rank: Rank::Five,
-                suit: Suit::Diamond,
-            },
-            ..Player::new()
-        },
-        Player {
-            card_played: JESTER,
-            ..Player::new()
-        },
-        Player {
-            card_played: Card {
-                rank: Rank::King,
-                suit: Suit::Diamond,
-            },
-            ..Player::new()
-        },
-        Player {
-            card_played: Card {
-                rank: Rank::Ace,
-                suit: Suit::Heart,
-            },
-            ..Player::new()
-        },
-        Player {
-            card_played: Card {
-                rank: Rank::Ace,
-                suit: Suit::Diamond,
-            },
-            ..Player::new()
-        },
-    ];
-    calc_winner_of_trick(&mut players, Suit::Spade);
-    assert_eq!(players[5].tricks, 1);
-}
+    assert_eq!(
+        calc_winner_of_trick(&trick("Je 5D Je KD AH AD"), Suit::Spade),
+        Some(5)
+    );
 
-pub fn calc_score(players: &mut Vec<Player>) {
-    for mut player in players {
-        if player.tricks == player.bet {
-            player.score += (2 + player.bet) as i16;
-            continue;
-        }
+    // Nobody played.
+    assert_eq!(calc_winner_of_trick(&[], Suit::Spade), None);
+}
 
-        let penalty = player.bet as i16 - player.tricks as i16;
-        player.score -= penalty.abs();
-    }
+/// Points each player earns for a round by seat, from their bet and the
+/// tricks they won.
+pub fn calc_points(bets: &[u8], tricks: &[u8], rule: &dyn ScoringRule) -> Vec<i16> {
+    bets.iter()
+        .zip(tricks)
+        .map(|(bet, tricks)| rule.score(*bet, *tricks))
+        .collect()
 }
 
 #[test]
-pub fn test_calc_score() {
-    let mut players: Vec<Player> = Vec::new();
-    let mut player = Player::new();
-
-    // First player bets 0 and gets 0 tricks, should have score of 3.
-    players.push(player.clone());
-
-    // Second player bets 1 and gets 0 tricks, should have score of -1.
-    player.bet = 1;
-    players.push(player.clone());
-
-    // Third player bets 1 and gets 2 tricks, should have score of -1.
-    player.tricks = 2;
-    players.push(player.clone());
-
-    calc_score(&mut players);
-    assert_eq!(players[0].score, 2);
-    assert_eq!(players[1].score, -1);
-    assert_eq!(players[2].score, -1);
+pub fn test_calc_points() {
+    use crate::scoring::{ClassicScoring, OfficialScoring};
+
+    // Bets 0 and gets 0 tricks, bets 1 and gets 0, bets 1 and gets 2.
+    let bets = [0, 1, 1];
+    let tricks = [0, 0, 2];
+    assert_eq!(
+        calc_points(&bets, &tricks, &ClassicScoring),
+        vec![2, -1, -1]
+    );
+    assert_eq!(
+        calc_points(&bets, &tricks, &OfficialScoring),
+        vec![20, -10, -10]
+    );
 }
 
-pub fn calc_winner(mut players: Vec<Player>) -> Player {
-    let mut winner = players.pop().unwrap();
-    for player in players {
-        if player.score > winner.score {
-            winner = player;
-        }
-    }
-    winner
+/// The seat with the highest score, None if there are no players.
+/// A tie goes to the player first in seat order.
+pub fn calc_winner(scores: &[i16]) -> Option<PlayerId> {
+    let best = scores.iter().max()?;
+    scores.iter().position(|score| score == best).map(PlayerId)
 }
 
 #[test]
 pub fn test_calc_winner() {
-    let mut players: Vec<Player> = Vec::new();
-    for _ in 0..6 {
-        players.push(Player::new());
-    }
-
-    players[0].score = 3;
-    players[1].score = -2;
-    players[2].score = 3;
-    players[3].score = 5;
-    players[4].score = 4;
-    players[5].score = 0;
-
-    // For now if there is a tie the player first in rotation will win.
-    let winner = calc_winner(players.clone());
-    assert_eq!(players[3].score, winner.score);
+    assert_eq!(calc_winner(&[3, -2, 3, 5, 4, 0]), Some(PlayerId(3)));
+    assert_eq!(calc_winner(&[3, -2, 3]), Some(PlayerId(0)));
+    assert!(calc_winner(&[]).is_none());
 }
@@ -0,0 +1,62 @@
+use crate::components::card::Card;
+use crate::components::deck::Deck;
+use crate::components::player::Player;
+use crate::config::GameConfig;
+use crate::error::WizardError;
+use crate::game::Phase;
+use crate::history::Round;
+use crate::table::PlayerId;
+use crate::Play;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// Everything needed to pick a game back up where it was left off.
+/// Made by `GameState::save` and turned back into a game by `GameState::resume`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SavedGame {
+    pub(crate) config: GameConfig,
+    pub(crate) seed: Option<u64>,
+    /// State of the game's rng, so the resumed game draws what it would have.
+    pub(crate) rng: ChaCha12Rng,
+    pub(crate) players: Vec<Player>,
+    pub(crate) bets: Vec<Option<u8>>,
+    pub(crate) tricks: Vec<u8>,
+    pub(crate) scores: Vec<i16>,
+    pub(crate) deck: Deck,
+    pub(crate) round_num: usize,
+    pub(crate) leader: PlayerId,
+    pub(crate) turn: PlayerId,
+    pub(crate) trump: Option<Card>,
+    pub(crate) trick: Vec<Play>,
+    pub(crate) history: Vec<Round>,
+    pub(crate) phase: Phase,
+}
+impl SavedGame {
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Write the game to `path` as JSON. The old file is only replaced once
+    /// the new one has been written in full.
+    pub fn write_to(&self, path: &Path) -> Result<(), WizardError> {
+        let json = serde_json::to_string_pretty(self).map_err(save_error)?;
+        let partial = path.with_extension("partial");
+        fs::write(&partial, json)
+            .and_then(|_| fs::rename(&partial, path))
+            .map_err(save_error)
+    }
+
+    pub fn read_from(path: &Path) -> Result<SavedGame, WizardError> {
+        let json = fs::read_to_string(path).map_err(save_error)?;
+        serde_json::from_str(&json).map_err(save_error)
+    }
+}
+
+fn save_error<E: ToString>(err: E) -> WizardError {
+    WizardError::SaveFile(err.to_string())
+}
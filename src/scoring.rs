@@ -0,0 +1,81 @@
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How many points a player gets at the end of a round.
+pub trait ScoringRule: fmt::Debug {
+    /// Points for a round where `bet` tricks were bet and `tricks` were won.
+    fn score(&self, bet: u8, tricks: u8) -> i16;
+}
+
+/// Published Wizard scoring: 20 points plus 10 per trick for an exact bet,
+/// otherwise minus 10 for every trick off.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct OfficialScoring;
+impl ScoringRule for OfficialScoring {
+    fn score(&self, bet: u8, tricks: u8) -> i16 {
+        if bet == tricks {
+            return 20 + 10 * tricks as i16;
+        }
+        -10 * (bet as i16 - tricks as i16).abs()
+    }
+}
+
+/// The original house scoring: 2 plus the bet when exact, otherwise minus 1
+/// for every trick off.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ClassicScoring;
+impl ScoringRule for ClassicScoring {
+    fn score(&self, bet: u8, tricks: u8) -> i16 {
+        if bet == tricks {
+            return 2 + bet as i16;
+        }
+        -(bet as i16 - tricks as i16).abs()
+    }
+}
+
+/// House variant that adds `bonus` points to a successful bet of zero.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ZeroBidBonus<S> {
+    pub rule: S,
+    pub bonus: i16,
+}
+impl<S: ScoringRule> ScoringRule for ZeroBidBonus<S> {
+    fn score(&self, bet: u8, tricks: u8) -> i16 {
+        let score = self.rule.score(bet, tricks);
+        if bet == 0 && tricks == 0 {
+            return score + self.bonus;
+        }
+        score
+    }
+}
+
+#[test]
+pub fn test_official_scoring() {
+    assert_eq!(OfficialScoring.score(0, 0), 20);
+    assert_eq!(OfficialScoring.score(3, 3), 50);
+    assert_eq!(OfficialScoring.score(1, 0), -10);
+    assert_eq!(OfficialScoring.score(1, 4), -30);
+}
+
+#[test]
+pub fn test_classic_scoring() {
+    assert_eq!(ClassicScoring.score(0, 0), 2);
+    assert_eq!(ClassicScoring.score(3, 3), 5);
+    assert_eq!(ClassicScoring.score(1, 0), -1);
+    assert_eq!(ClassicScoring.score(1, 4), -3);
+}
+
+#[test]
+pub fn test_zero_bid_bonus() {
+    let rule = ZeroBidBonus {
+        rule: OfficialScoring,
+        bonus: 10,
+    };
+    assert_eq!(rule.score(0, 0), 30);
+    assert_eq!(rule.score(0, 1), -10);
+    assert_eq!(rule.score(2, 2), 40);
+}
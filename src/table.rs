@@ -0,0 +1,140 @@
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Stable identity of a seat at the table. Seats are numbered clockwise from 0
+/// in the order of `GameConfig::players` and never move during a game.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PlayerId(pub usize);
+impl PlayerId {
+    /// Index of the seat in `GameState::players`.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "seat {}", self.0 + 1)
+    }
+}
+
+/// Fixed seats around the table and the dealer button. The seats stay put,
+/// only the dealer moves one seat to the left each round.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Table {
+    num_seats: usize,
+    dealer: PlayerId,
+}
+impl Table {
+    pub fn new(num_seats: usize) -> Table {
+        Table {
+            num_seats,
+            dealer: PlayerId(0),
+        }
+    }
+
+    pub fn num_seats(&self) -> usize {
+        self.num_seats
+    }
+
+    pub fn dealer(&self) -> PlayerId {
+        self.dealer
+    }
+
+    /// Move the dealer button to the dealer of `round_num`, counting from 1.
+    /// The first seat deals the first round.
+    pub fn set_round(&mut self, round_num: usize) {
+        self.dealer = PlayerId(round_num.saturating_sub(1) % self.num_seats);
+    }
+
+    /// The seat to the left of `id`, who plays after them.
+    pub fn left_of(&self, id: PlayerId) -> PlayerId {
+        PlayerId((id.0 + 1) % self.num_seats)
+    }
+
+    /// Every seat starting from the first.
+    pub fn seats(&self) -> Seats {
+        self.order_from(PlayerId(0))
+    }
+
+    /// Order cards are dealt and bets are placed: starting left of the dealer,
+    /// the dealer goes last.
+    pub fn betting_order(&self) -> Seats {
+        self.order_from(self.left_of(self.dealer))
+    }
+
+    /// Order cards are played into a trick led by `leader`.
+    pub fn play_order(&self, leader: PlayerId) -> Seats {
+        self.order_from(leader)
+    }
+
+    fn order_from(&self, first: PlayerId) -> Seats {
+        Seats {
+            next: first.0,
+            remaining: self.num_seats,
+            num_seats: self.num_seats,
+        }
+    }
+}
+
+/// Iterator over every seat once, going left around the table.
+#[derive(Clone, Debug)]
+pub struct Seats {
+    next: usize,
+    remaining: usize,
+    num_seats: usize,
+}
+impl Iterator for Seats {
+    type Item = PlayerId;
+
+    fn next(&mut self) -> Option<PlayerId> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let id = PlayerId(self.next);
+        self.next = (self.next + 1) % self.num_seats;
+        self.remaining -= 1;
+        Some(id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl ExactSizeIterator for Seats {}
+
+#[test]
+pub fn test_betting_order() {
+    let ids = |seats: &[usize]| seats.iter().map(|seat| PlayerId(*seat)).collect::<Vec<_>>();
+    let mut table = Table::new(4);
+    assert_eq!(table.seats().collect::<Vec<_>>(), ids(&[0, 1, 2, 3]));
+
+    // First seat deals the first round, betting starts to its left.
+    table.set_round(1);
+    assert_eq!(table.dealer(), PlayerId(0));
+    assert_eq!(
+        table.betting_order().collect::<Vec<_>>(),
+        ids(&[1, 2, 3, 0])
+    );
+
+    // Dealer moves left every round and wraps around.
+    table.set_round(4);
+    assert_eq!(table.dealer(), PlayerId(3));
+    assert_eq!(
+        table.betting_order().collect::<Vec<_>>(),
+        ids(&[0, 1, 2, 3])
+    );
+    table.set_round(6);
+    assert_eq!(table.dealer(), PlayerId(1));
+    assert_eq!(table.betting_order().len(), 4);
+}
+
+#[test]
+pub fn test_play_order() {
+    let table = Table::new(3);
+    let order: Vec<PlayerId> = table.play_order(PlayerId(2)).collect();
+    assert_eq!(order, vec![PlayerId(2), PlayerId(0), PlayerId(1)]);
+    assert_eq!(table.left_of(PlayerId(2)), PlayerId(0));
+}
@@ -0,0 +1,226 @@
+use crate::components::card::{Card, Rank, Suit};
+use crate::components::card_set::CardSet;
+use crate::events::{Event, GameObserver};
+use crate::game::GameView;
+use crate::table::PlayerId;
+use crate::{lead_suit, Play};
+
+/// Memory of the cards seen in one round: who played what, which suits each
+/// seat has shown it is out of, and what is still to come. Fill it from a
+/// `GameView` with `from_view`, or attach it as an observer to follow a game.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CardTracker {
+    /// Cards played by seat.
+    played: Vec<CardSet>,
+    /// Cards each seat can't be holding because it didn't follow their suit.
+    voids: Vec<CardSet>,
+    trump: Option<Card>,
+    trump_suit: Suit,
+    trick: Vec<Card>,
+}
+impl CardTracker {
+    pub fn new() -> CardTracker {
+        CardTracker {
+            played: Vec::new(),
+            voids: Vec::new(),
+            trump: None,
+            trump_suit: Suit::Suitless,
+            trick: Vec::new(),
+        }
+    }
+
+    /// Everything `view` shows of the current round.
+    pub fn from_view(view: &GameView) -> CardTracker {
+        let mut tracker = CardTracker::new();
+        tracker.start_round(view.trump);
+        tracker.trump_suit = view.trump_suit();
+        for trick in view.past_tricks {
+            trick.plays.iter().for_each(|play| tracker.record(*play));
+            tracker.end_trick();
+        }
+        view.trick.iter().for_each(|play| tracker.record(*play));
+        tracker
+    }
+
+    /// Forget the last round, `trump` is the card flipped for the new one.
+    pub fn start_round(&mut self, trump: Option<Card>) {
+        *self = CardTracker::new();
+        self.trump = trump;
+        self.trump_suit = trump.map_or(Suit::Suitless, |card| card.suit);
+    }
+
+    /// The dealer picked `suit` after flipping a Wizard.
+    pub fn set_trump_suit(&mut self, suit: Suit) {
+        self.trump_suit = suit;
+    }
+
+    /// Note a card played into the current trick. Playing something other
+    /// than the lead suit, Wizards and Jesters aside, shows the seat is out of it.
+    pub fn record(&mut self, play: Play) {
+        let seat = play.seat.index();
+        if self.played.len() <= seat {
+            self.played.resize(seat + 1, CardSet::EMPTY);
+            self.voids.resize(seat + 1, CardSet::EMPTY);
+        }
+
+        let card = play.card;
+        let lead = lead_suit(&self.trick);
+        let free = matches!(card.rank, Rank::Wizard | Rank::Jester);
+        if lead != Suit::Suitless && !free && card.suit != lead {
+            self.voids[seat] |= CardSet::suit(lead);
+        }
+        self.played[seat].insert(card.id);
+        self.trick.push(card);
+    }
+
+    /// The current trick is complete, the next card played leads.
+    pub fn end_trick(&mut self) {
+        self.trick.clear();
+    }
+
+    /// Every card played this round.
+    pub fn played(&self) -> CardSet {
+        self.played
+            .iter()
+            .fold(CardSet::EMPTY, |all, cards| all | *cards)
+    }
+
+    pub fn played_by(&self, seat: PlayerId) -> CardSet {
+        self.played.get(seat.index()).copied().unwrap_or_default()
+    }
+
+    /// Cards `seat` can't be holding because it didn't follow their suit.
+    pub fn ruled_out(&self, seat: PlayerId) -> CardSet {
+        self.voids.get(seat.index()).copied().unwrap_or_default()
+    }
+
+    /// True if `seat` has shown it has no cards of `suit` left.
+    pub fn is_void(&self, seat: PlayerId, suit: Suit) -> bool {
+        suit != Suit::Suitless && CardSet::suit(suit) - self.ruled_out(seat) == CardSet::EMPTY
+    }
+
+    /// Cards that haven't been played or flipped for trump. Some may be in
+    /// the hand of whoever is asking, see `unseen`.
+    pub fn remaining(&self) -> CardSet {
+        let mut remaining = !self.played();
+        if let Some(trump) = self.trump {
+            remaining.remove(trump.id);
+        }
+        remaining
+    }
+
+    /// Cards that could be in the other players' hands, or weren't dealt.
+    pub fn unseen(&self, hand: CardSet) -> CardSet {
+        self.remaining() - hand
+    }
+
+    /// The highest trump that hasn't been played or flipped, None without a
+    /// trump suit or once they are all gone.
+    pub fn highest_remaining_trump(&self) -> Option<Card> {
+        self.remaining().highest_of_suit(self.trump_suit)
+    }
+
+    /// Wizards that haven't been played or flipped yet.
+    pub fn wizards_out(&self) -> usize {
+        (self.remaining() & CardSet::WIZARDS).len()
+    }
+
+    /// Jesters that haven't been played or flipped yet.
+    pub fn jesters_out(&self) -> usize {
+        (self.remaining() & CardSet::JESTERS).len()
+    }
+}
+
+impl Default for CardTracker {
+    fn default() -> Self {
+        CardTracker::new()
+    }
+}
+
+/// Follows a game as it is played.
+impl GameObserver for CardTracker {
+    fn notify(&mut self, event: &Event) {
+        match event {
+            Event::TrumpRevealed(trump) => self.start_round(*trump),
+            Event::TrumpSuitChosen { suit, .. } => self.set_trump_suit(*suit),
+            Event::CardPlayed(play) => self.record(*play),
+            Event::TrickWon(_) => self.end_trick(),
+            _ => {}
+        }
+    }
+}
+
+#[test]
+pub fn test_card_tracker() {
+    let card = |code: &str| code.parse::<Card>().unwrap();
+    let play = |seat, code| Play {
+        card: card(code),
+        seat: PlayerId(seat),
+    };
+
+    let mut tracker = CardTracker::new();
+    tracker.start_round(Some(card("AS")));
+    assert_eq!(tracker.highest_remaining_trump(), Some(card("KS")));
+    assert_eq!(tracker.wizards_out(), 4);
+
+    // Seat 2 follows with a Jester, seat 0 can't follow hearts.
+    tracker.record(play(1, "5H"));
+    tracker.record(play(2, "Je"));
+    tracker.record(play(0, "KS"));
+    tracker.end_trick();
+    assert!(tracker.is_void(PlayerId(0), Suit::Heart));
+    assert!(!tracker.is_void(PlayerId(2), Suit::Heart));
+    assert!(!tracker.is_void(PlayerId(0), Suit::Spade));
+    assert_eq!(tracker.highest_remaining_trump(), Some(card("QS")));
+    assert_eq!(tracker.jesters_out(), 3);
+
+    // A Wizard lead doesn't ask anyone to follow.
+    tracker.record(play(0, "W"));
+    tracker.record(play(1, "2C"));
+    assert!(!tracker.is_void(PlayerId(1), Suit::Club));
+    assert_eq!(tracker.wizards_out(), 3);
+
+    assert_eq!(tracker.played().len(), 5);
+    assert_eq!(
+        tracker.played_by(PlayerId(0)),
+        [card("KS"), card("W")].iter().copied().collect()
+    );
+    let hand: CardSet = [card("QS")].iter().copied().collect();
+    assert_eq!(tracker.unseen(hand).len(), 60 - 5 - 1 - 1);
+    assert!(tracker.ruled_out(PlayerId(0)).contains(card("AH").id));
+
+    // A new round starts from scratch.
+    tracker.start_round(None);
+    assert!(tracker.played().is_empty());
+    assert_eq!(tracker.highest_remaining_trump(), None);
+}
+
+#[test]
+pub fn test_card_tracker_follows_game() {
+    use crate::game::{play_until, test_config, GameState, Phase};
+    use std::sync::mpsc;
+
+    let config = test_config(3).num_rounds(3).seed(11).build().unwrap();
+    let mut game = GameState::new(config).unwrap();
+    let (sender, events) = mpsc::channel();
+    game.add_observer(Box::new(sender));
+
+    // Fed the events or rebuilt from a view, the trackers agree.
+    let mut tracker = CardTracker::new();
+    play_until(&mut game, |game| {
+        events.try_iter().for_each(|event| tracker.notify(&event));
+
+        let view = game.view(game.turn());
+        let rebuilt = CardTracker::from_view(&view);
+        assert_eq!(tracker.played(), view.played);
+        assert_eq!(rebuilt.played(), view.played);
+        for seat in game.table().seats() {
+            assert_eq!(tracker.ruled_out(seat), rebuilt.ruled_out(seat));
+        }
+        assert_eq!(
+            tracker.highest_remaining_trump(),
+            rebuilt.highest_remaining_trump()
+        );
+        game.phase() == Phase::GameOver
+    });
+}